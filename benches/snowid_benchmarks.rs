@@ -21,7 +21,7 @@ pub fn node_bits_comparison(c: &mut Criterion) {
         group.bench_function(
             format!("bits_{node_bits}_nodes_{max_nodes}_seq_{max_sequence}"),
             |b| {
-                let generator = SnowID::with_config(1, config).unwrap();
+                let generator = SnowID::with_config(1, config.clone()).unwrap();
                 b.iter(|| {
                     black_box(generator.generate());
                 });
@@ -66,7 +66,7 @@ pub fn overflow_stress_concurrent_lockfree(c: &mut Criterion) {
         for &per_thread in &[64usize, 256] {
             group.bench_function(format!("threads/{threads}/per_thread/{per_thread}"), |b| {
                 b.iter_batched(
-                    || std::sync::Arc::new(SnowID::with_config(1, cfg).unwrap()),
+                    || std::sync::Arc::new(SnowID::with_config(1, cfg.clone()).unwrap()),
                     |generator| {
                         let mut handles = Vec::with_capacity(threads);
                         for _ in 0..threads {
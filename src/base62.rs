@@ -4,6 +4,15 @@
 //! - `encode_array`: Returns [u8; 11] + length
 //! - `encode_into`: Writes to caller buffer
 //! - `encode`: Convenience String wrapper
+//!
+//! Fixed-width variants (`encode_fixed*`, `decode_fixed`) zero-pad to 11 characters so that
+//! sorting the strings byte-wise matches sorting the IDs numerically.
+//!
+//! Checked variants (`encode_checked*`, `decode_checked`) append a 2-character checksum so
+//! corrupted or truncated input is detected instead of decoding to an unrelated ID.
+//!
+//! Bulk variants (`encode_many`, `decode_many`, `decode_iter`) handle separator-delimited
+//! lists of IDs in one contiguous buffer.
 
 use std::error::Error;
 use std::fmt;
@@ -11,12 +20,77 @@
 /// Maximum size needed for u64 in base62 encoding (11 bytes)
 pub const MAX_LEN: usize = 11;
 
+/// Number of checksum characters appended by the checked variants
+pub const CHECKSUM_LEN: usize = 2;
+
+/// Maximum size of a checked base62 encoding (13 bytes)
+pub const MAX_CHECKED_LEN: usize = MAX_LEN + CHECKSUM_LEN;
+
+/// Base62 alphabet: digits, uppercase, then lowercase (ASCII order, so fixed-width output sorts)
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Marker for bytes outside the alphabet in the decode table
+const INVALID: u8 = u8::MAX;
+
+/// Byte to digit value lookup table
+const DECODE_TABLE: [u8; 256] = decode_table();
+
+const fn decode_table() -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE) lookup table
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
 /// Zero-allocation base62 encoding to a fixed-size array
 /// Returns the array and the actual length of encoded bytes
 #[inline]
-pub fn encode_array(id: u64) -> ([u8; MAX_LEN], usize) {
+pub const fn encode_array(id: u64) -> ([u8; MAX_LEN], usize) {
+    let mut digits = [0u8; MAX_LEN];
+    let mut n = id;
+    let mut pos = MAX_LEN;
+    loop {
+        pos -= 1;
+        digits[pos] = ALPHABET[(n % 62) as usize];
+        n /= 62;
+        if n == 0 {
+            break;
+        }
+    }
+
+    let len = MAX_LEN - pos;
     let mut buf = [0u8; MAX_LEN];
-    let len = base62::encode_bytes(id, &mut buf).unwrap();
+    let mut i = 0;
+    while i < len {
+        buf[i] = digits[pos + i];
+        i += 1;
+    }
     (buf, len)
 }
 
@@ -24,7 +98,8 @@
 /// Returns a str slice of the encoded portion
 #[inline]
 pub fn encode_into(id: u64, buf: &mut [u8; MAX_LEN]) -> &str {
-    let len = base62::encode_bytes(id, buf).unwrap();
+    let len;
+    (*buf, len) = encode_array(id);
     // base62 output is always valid ASCII
     std::str::from_utf8(&buf[..len]).unwrap()
 }
@@ -38,53 +113,291 @@ pub fn encode(id: u64) -> String {
     std::str::from_utf8(&buf[..len]).unwrap().to_owned()
 }
 
+/// Zero-allocation fixed-width base62 encoding, left-padded with '0' to `MAX_LEN` characters
+/// Byte order of the output matches numeric order of the IDs
+#[inline]
+pub fn encode_fixed_array(id: u64) -> [u8; MAX_LEN] {
+    let (buf, len) = encode_array(id);
+    let mut fixed = [b'0'; MAX_LEN];
+    fixed[MAX_LEN - len..].copy_from_slice(&buf[..len]);
+    fixed
+}
+
+/// Zero-allocation fixed-width base62 encoding into caller-provided buffer
+#[inline]
+pub fn encode_fixed_into(id: u64, buf: &mut [u8; MAX_LEN]) -> &str {
+    *buf = encode_fixed_array(id);
+    // base62 output is always valid ASCII
+    std::str::from_utf8(buf).unwrap()
+}
+
+/// Fixed-width base62 encode with String allocation (convenience wrapper)
+/// For hot paths, prefer `encode_fixed_array` or `encode_fixed_into`
+#[inline]
+pub fn encode_fixed(id: u64) -> String {
+    let buf = encode_fixed_array(id);
+    // base62 output is always valid ASCII
+    std::str::from_utf8(&buf).unwrap().to_owned()
+}
+
+/// Decode a fixed-width base62 string, rejecting input that is not exactly `MAX_LEN` characters
+pub fn decode_fixed(encoded: &str) -> Result<u64, DecodeError> {
+    if encoded.len() != MAX_LEN {
+        return Err(DecodeError::InvalidLength { len: encoded.len() });
+    }
+    decode(encoded)
+}
+
+/// Compute the 2-character checksum of an ID (CRC-32 of its big-endian bytes, reduced mod 62^2)
+#[inline]
+pub fn checksum(id: u64) -> [u8; CHECKSUM_LEN] {
+    let mut crc = !0u32;
+    for byte in id.to_be_bytes() {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    let sum = (!crc % (62 * 62)) as usize;
+    [ALPHABET[sum / 62], ALPHABET[sum % 62]]
+}
+
+/// Zero-allocation checked base62 encoding: base62 body followed by a 2-character checksum
+/// Returns the array and the actual length of encoded bytes
+#[inline]
+pub fn encode_checked_array(id: u64) -> ([u8; MAX_CHECKED_LEN], usize) {
+    let (body, len) = encode_array(id);
+    let mut buf = [0u8; MAX_CHECKED_LEN];
+    buf[..len].copy_from_slice(&body[..len]);
+    buf[len..len + CHECKSUM_LEN].copy_from_slice(&checksum(id));
+    (buf, len + CHECKSUM_LEN)
+}
+
+/// Zero-allocation checked base62 encoding into caller-provided buffer
+#[inline]
+pub fn encode_checked_into(id: u64, buf: &mut [u8; MAX_CHECKED_LEN]) -> &str {
+    let len;
+    (*buf, len) = encode_checked_array(id);
+    // base62 output is always valid ASCII
+    std::str::from_utf8(&buf[..len]).unwrap()
+}
+
+/// Checked base62 encode with String allocation (convenience wrapper)
+/// For hot paths, prefer `encode_checked_array` or `encode_checked_into`
+#[inline]
+pub fn encode_checked(id: u64) -> String {
+    let (buf, len) = encode_checked_array(id);
+    // base62 output is always valid ASCII
+    std::str::from_utf8(&buf[..len]).unwrap().to_owned()
+}
+
+/// Decode a checked base62 string, failing with `ChecksumMismatch` on corrupted or truncated input
+pub fn decode_checked(encoded: &str) -> Result<u64, DecodeError> {
+    if encoded.len() <= CHECKSUM_LEN || !encoded.is_char_boundary(encoded.len() - CHECKSUM_LEN) {
+        return Err(DecodeError::InvalidLength { len: encoded.len() });
+    }
+    let (body, sum) = encoded.split_at(encoded.len() - CHECKSUM_LEN);
+    let id = decode(body)?;
+    if sum.as_bytes() != checksum(id) {
+        return Err(DecodeError::ChecksumMismatch);
+    }
+    Ok(id)
+}
+
 /// Decode a base62 string to a u64, handling potential overflow
-pub fn decode(encoded: &str) -> Result<u64, DecodeError> {
-    let decoded = base62::decode(encoded).map_err(DecodeError::from)?;
+pub const fn decode(encoded: &str) -> Result<u64, DecodeError> {
+    let bytes = encoded.as_bytes();
+    if bytes.is_empty() {
+        return Err(DecodeError::Empty);
+    }
+    if bytes.len() > MAX_LEN {
+        return Err(DecodeError::TooLong { len: bytes.len() });
+    }
 
-    // Check if the decoded value fits in a u64
-    let Ok(value) = u64::try_from(decoded) else {
-        return Err(DecodeError::Overflow);
+    let mut value = 0u64;
+    let mut position = 0;
+    while position < bytes.len() {
+        let byte = bytes[position];
+        let digit = DECODE_TABLE[byte as usize];
+        if digit == INVALID {
+            return Err(DecodeError::InvalidCharacter { byte, position });
+        }
+        value = match value.checked_mul(62) {
+            Some(v) => match v.checked_add(digit as u64) {
+                Some(v) => v,
+                None => return Err(DecodeError::Overflow),
+            },
+            None => return Err(DecodeError::Overflow),
+        };
+        position += 1;
+    }
+    Ok(value)
+}
+
+/// Encode many IDs into one contiguous buffer, separated by an ASCII `separator`
+/// Appends to `out` without a trailing separator; reuse the buffer across batches
+///
+/// Fails with `InvalidSeparator`, leaving `out` unchanged, if `separator` is not ASCII or
+/// belongs to the base62 alphabet, since the output could not be split again
+pub fn encode_many(ids: &[u64], out: &mut Vec<u8>, separator: u8) -> Result<(), DecodeError> {
+    check_separator(separator)?;
+    out.reserve(ids.len() * (MAX_LEN + 1));
+    for (i, &id) in ids.iter().enumerate() {
+        if i > 0 {
+            out.push(separator);
+        }
+        let (buf, len) = encode_array(id);
+        out.extend_from_slice(&buf[..len]);
+    }
+    Ok(())
+}
+
+/// Decode a list of IDs separated by an ASCII `separator`
+/// A single trailing separator (e.g. a final newline) is allowed; empty input yields no IDs
+///
+/// A separator `encode_many` rejects fails with `InvalidSeparator` at index 0
+pub fn decode_many(input: &str, separator: u8) -> Result<Vec<u64>, BulkDecodeError> {
+    decode_iter(input, separator).collect()
+}
+
+/// Streaming variant of `decode_many`, yielding each ID (or error) as it is parsed
+///
+/// A separator `encode_many` rejects yields a single `InvalidSeparator` error
+pub fn decode_iter(input: &str, separator: u8) -> DecodeIter<'_> {
+    if let Err(error) = check_separator(separator) {
+        return DecodeIter {
+            rest: None,
+            separator,
+            index: 0,
+            error: Some(error),
+        };
+    }
+    let input = match input.as_bytes().last() {
+        Some(&last) if last == separator => &input[..input.len() - 1],
+        _ => input,
     };
+    DecodeIter {
+        rest: (!input.is_empty()).then_some(input),
+        separator,
+        index: 0,
+        error: None,
+    }
+}
 
-    Ok(value)
+/// Reject separators that would make the encoded list ambiguous
+#[inline]
+fn check_separator(separator: u8) -> Result<(), DecodeError> {
+    if separator.is_ascii() && DECODE_TABLE[separator as usize] == INVALID {
+        Ok(())
+    } else {
+        Err(DecodeError::InvalidSeparator { byte: separator })
+    }
+}
+
+/// Iterator returned by `decode_iter`
+#[derive(Debug, Clone)]
+pub struct DecodeIter<'a> {
+    rest: Option<&'a str>,
+    separator: u8,
+    index: usize,
+    // Rejected separator, reported once instead of any entries
+    error: Option<DecodeError>,
+}
+
+impl Iterator for DecodeIter<'_> {
+    type Item = Result<u64, BulkDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(BulkDecodeError { index: 0, error }));
+        }
+        let rest = self.rest?;
+        // Splitting at an ASCII byte always lands on a char boundary
+        let entry = match rest.bytes().position(|b| b == self.separator) {
+            Some(end) => {
+                self.rest = Some(&rest[end + 1..]);
+                &rest[..end]
+            }
+            None => {
+                self.rest = None;
+                rest
+            }
+        };
+        let index = self.index;
+        self.index += 1;
+        Some(decode(entry).map_err(|error| BulkDecodeError { index, error }))
+    }
 }
 
-/// Error type for base62 decoding operations
-#[derive(Debug)]
+/// Error from bulk decoding, with the zero-based index of the bad entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkDecodeError {
+    /// Index of the entry that failed to decode
+    pub index: usize,
+    /// Reason the entry failed
+    pub error: DecodeError,
+}
+
+impl fmt::Display for BulkDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entry {}: {}", self.index, self.error)
+    }
+}
+
+impl Error for BulkDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error type for base62 (and other encoding) decoding operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DecodeError {
-    /// Invalid character in base62 string
-    InvalidCharacter,
+    /// Input is empty
+    Empty,
+    /// Byte outside the alphabet at the given position
+    InvalidCharacter { byte: u8, position: usize },
+    /// Input has more characters than any u64 can need
+    TooLong { len: usize },
     /// Decoded value would overflow u64
     Overflow,
-    /// Other decoding error from base62 crate
-    Other(base62::DecodeError),
+    /// Fixed-width or checked input has the wrong number of characters
+    InvalidLength { len: usize },
+    /// Check symbol or checksum does not match the decoded value
+    ChecksumMismatch,
+    /// Bulk separator is not ASCII or belongs to the alphabet
+    InvalidSeparator { byte: u8 },
 }
 
 impl fmt::Display for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            DecodeError::InvalidCharacter => write!(f, "Invalid base62 character"),
+            DecodeError::Empty => write!(f, "Encoded input is empty"),
+            DecodeError::InvalidCharacter { byte, position } if byte.is_ascii_graphic() => {
+                write!(
+                    f,
+                    "Invalid character '{}' at position {}",
+                    *byte as char, position
+                )
+            }
+            DecodeError::InvalidCharacter { byte, position } => {
+                write!(f, "Invalid byte 0x{:02x} at position {}", byte, position)
+            }
+            DecodeError::TooLong { len } => {
+                write!(f, "Encoded input has too many characters ({})", len)
+            }
             DecodeError::Overflow => write!(f, "Decoded value would overflow u64"),
-            DecodeError::Other(e) => write!(f, "Base62 decode error: {}", e),
+            DecodeError::InvalidLength { len } => {
+                write!(f, "Invalid encoded length of {} characters", len)
+            }
+            DecodeError::ChecksumMismatch => write!(f, "Checksum does not match decoded value"),
+            DecodeError::InvalidSeparator { byte } => write!(
+                f,
+                "Separator 0x{:02x} must be ASCII and outside the alphabet",
+                byte
+            ),
         }
     }
 }
 
-impl Error for DecodeError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match self {
-            DecodeError::Other(e) => Some(e),
-            _ => None,
-        }
-    }
-}
-
-impl From<base62::DecodeError> for DecodeError {
-    fn from(err: base62::DecodeError) -> Self {
-        DecodeError::Other(err)
-    }
-}
+impl Error for DecodeError {}
 
 #[cfg(test)]
 mod tests {
@@ -130,4 +443,271 @@ fn test_decode_error() {
         // Invalid characters
         assert!(decode("!!!").is_err());
     }
+
+    #[test]
+    fn test_decode_error_details() {
+        assert_eq!(decode(""), Err(DecodeError::Empty));
+        assert_eq!(
+            decode("abc!def"),
+            Err(DecodeError::InvalidCharacter {
+                byte: b'!',
+                position: 3
+            })
+        );
+        assert_eq!(
+            decode("000000000001"),
+            Err(DecodeError::TooLong { len: 12 })
+        );
+        assert_eq!(decode("LygHa16AHYG"), Err(DecodeError::Overflow));
+        assert_eq!(decode("LygHa16AHYF"), Ok(u64::MAX));
+        assert_eq!(
+            decode("abc!def").unwrap_err().to_string(),
+            "Invalid character '!' at position 3"
+        );
+        assert_eq!(
+            decode("ab\u{e9}").unwrap_err().to_string(),
+            "Invalid byte 0xc3 at position 2"
+        );
+    }
+
+    #[test]
+    fn test_const_codec() {
+        const ENCODED: ([u8; MAX_LEN], usize) = encode_array(3843);
+        const DECODED: Result<u64, DecodeError> = decode("zz");
+        assert_eq!(&ENCODED.0[..ENCODED.1], b"zz");
+        assert_eq!(DECODED, Ok(3843));
+    }
+
+    #[test]
+    fn test_known_encodings() {
+        assert_eq!(encode(0), "0");
+        assert_eq!(encode(61), "z");
+        assert_eq!(encode(62), "10");
+        assert_eq!(encode(u64::MAX), "LygHa16AHYF");
+        assert_eq!(encode(151819733950271234), "BDKpYLONVK");
+    }
+
+    #[test]
+    fn test_encode_fixed_width() {
+        assert_eq!(encode_fixed(0), "00000000000");
+        assert_eq!(encode_fixed(61), "0000000000z");
+        assert_eq!(encode_fixed(u64::MAX), encode(u64::MAX));
+        for id in [1u64, 62, 12345678901234, u64::MAX] {
+            assert_eq!(encode_fixed(id).len(), MAX_LEN);
+            assert_eq!(decode_fixed(&encode_fixed(id)).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_encode_fixed_sorts_like_ids() {
+        let mut ids = vec![
+            0u64,
+            1,
+            9,
+            10,
+            35,
+            36,
+            61,
+            62,
+            3843,
+            3844,
+            1 << 40,
+            u64::MAX - 1,
+        ];
+        ids.extend((0..64).map(|i| 1u64 << i));
+        ids.sort_unstable();
+        let encoded: Vec<String> = ids.iter().map(|&id| encode_fixed(id)).collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+    }
+
+    #[test]
+    fn test_encode_fixed_variants_match() {
+        let id = 98765432109876u64;
+        let mut buf = [0u8; MAX_LEN];
+        assert_eq!(encode_fixed_into(id, &mut buf), encode_fixed(id));
+        assert_eq!(&encode_fixed_array(id), encode_fixed(id).as_bytes());
+    }
+
+    #[test]
+    fn test_decode_fixed_strict_length() {
+        assert!(matches!(
+            decode_fixed("abc"),
+            Err(DecodeError::InvalidLength { len: 3 })
+        ));
+        assert!(matches!(
+            decode_fixed("000000000001"),
+            Err(DecodeError::InvalidLength { len: 12 })
+        ));
+        assert!(matches!(
+            decode_fixed("zzzzzzzzzzz"),
+            Err(DecodeError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_checked_roundtrip() {
+        for id in [0u64, 1, 62, 151819733950271234, u64::MAX] {
+            let encoded = encode_checked(id);
+            assert_eq!(encoded.len(), encode(id).len() + CHECKSUM_LEN);
+            assert!(encoded.starts_with(&encode(id)));
+            assert_eq!(decode_checked(&encoded).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_checked_variants_match() {
+        let id = 98765432109876u64;
+        let (arr, len) = encode_checked_array(id);
+        let mut buf = [0u8; MAX_CHECKED_LEN];
+        assert_eq!(&arr[..len], encode_checked(id).as_bytes());
+        assert_eq!(encode_checked_into(id, &mut buf), encode_checked(id));
+    }
+
+    #[test]
+    fn test_checked_detects_corruption() {
+        let encoded = encode_checked(151819733950271234);
+        let bytes = encoded.as_bytes();
+
+        // Every single-character substitution is detected
+        for pos in 0..bytes.len() {
+            for &c in ALPHABET {
+                if c == bytes[pos] {
+                    continue;
+                }
+                let mut corrupted = bytes.to_vec();
+                corrupted[pos] = c;
+                let corrupted = String::from_utf8(corrupted).unwrap();
+                assert!(decode_checked(&corrupted).is_err(), "{corrupted}");
+            }
+        }
+
+        // Truncation is detected
+        for len in 0..encoded.len() {
+            assert!(decode_checked(&encoded[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unchecked_input_rejected() {
+        let id = 151819733950271234u64;
+        assert!(matches!(
+            decode_checked(&encode(id)),
+            Err(DecodeError::ChecksumMismatch)
+        ));
+        assert!(matches!(
+            decode_checked("ab"),
+            Err(DecodeError::InvalidLength { len: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_encode_many() {
+        let ids = [0u64, 61, 62, u64::MAX];
+        let mut out = Vec::new();
+        encode_many(&ids, &mut out, b',').unwrap();
+        assert_eq!(out, b"0,z,10,LygHa16AHYF");
+
+        // Appends to existing content
+        out.push(b',');
+        encode_many(&[1], &mut out, b',').unwrap();
+        assert_eq!(out, b"0,z,10,LygHa16AHYF,1");
+
+        let mut empty = Vec::new();
+        encode_many(&[], &mut empty, b'\n').unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_roundtrip() {
+        let ids: Vec<u64> = (0..1000u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        let mut out = Vec::new();
+        encode_many(&ids, &mut out, b'\n').unwrap();
+        let text = std::str::from_utf8(&out).unwrap();
+        assert_eq!(decode_many(text, b'\n').unwrap(), ids);
+    }
+
+    #[test]
+    fn test_decode_many_edge_cases() {
+        assert_eq!(decode_many("", b'\n').unwrap(), Vec::<u64>::new());
+        assert_eq!(decode_many("z\n10\n", b'\n').unwrap(), vec![61, 62]);
+        assert_eq!(
+            decode_many("z\n\n10", b'\n'),
+            Err(BulkDecodeError {
+                index: 1,
+                error: DecodeError::Empty
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_many_rejects_alphabet_separator() {
+        let mut out = b"kept".to_vec();
+        assert_eq!(
+            encode_many(&[1, 2], &mut out, b'a'),
+            Err(DecodeError::InvalidSeparator { byte: b'a' })
+        );
+        assert_eq!(out, b"kept");
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_separator() {
+        let error = BulkDecodeError {
+            index: 0,
+            error: DecodeError::InvalidSeparator { byte: 0xe9 },
+        };
+        let mut iter = decode_iter("1\u{e9}2", 0xe9);
+        assert_eq!(iter.next(), Some(Err(error)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(
+            decode_many("1a2", b'a').unwrap_err().error,
+            DecodeError::InvalidSeparator { byte: b'a' }
+        );
+        assert_eq!(
+            error.error.to_string(),
+            "Separator 0xe9 must be ASCII and outside the alphabet"
+        );
+    }
+
+    #[test]
+    fn test_decode_many_non_ascii_entries() {
+        // Multi-byte characters stay inside their entry and fail to decode there
+        assert_eq!(
+            decode_many("1,\u{e9},2", b','),
+            Err(BulkDecodeError {
+                index: 1,
+                error: DecodeError::InvalidCharacter {
+                    byte: 0xc3,
+                    position: 0
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_iter_reports_index() {
+        let mut iter = decode_iter("1,2,x!,4", b',');
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(2)));
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!(
+            err,
+            BulkDecodeError {
+                index: 2,
+                error: DecodeError::InvalidCharacter {
+                    byte: b'!',
+                    position: 1
+                }
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Entry 2: Invalid character '!' at position 1"
+        );
+        assert_eq!(iter.next(), Some(Ok(4)));
+        assert_eq!(iter.next(), None);
+    }
 }
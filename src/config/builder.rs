@@ -1,6 +1,10 @@
 //! SnowIDConfig builder for constructing configuration
 
+use std::ops::RangeInclusive;
+
+use super::roles::{NodeRole, check_fits, insert_role};
 use super::{SnowIDConfig, SnowIDConfigError};
+use crate::SnowID;
 
 /// Default configuration values
 pub(super) const DEFAULT_NODE_BITS: u8 = 10;
@@ -8,39 +12,115 @@
 pub(super) const DEFAULT_SPIN_ENABLED: bool = true;
 pub(super) const DEFAULT_SPIN_LOOPS: u32 = 64;
 pub(super) const DEFAULT_SPIN_YIELD_EVERY: u32 = 16;
+pub(super) const DEFAULT_TICK_MS: u64 = 1;
+
+/// Total bits available to a JavaScript-safe layout (`Number.MAX_SAFE_INTEGER` = 2^53 - 1)
+const JS_SAFE_TOTAL_BITS: u8 = 53;
 
 /// Builder for SnowIDConfig
 #[derive(Debug)]
 pub struct SnowIDConfigBuilder {
+    pub(super) timestamp_bits: u8,
     pub(super) node_bits: u8,
+    pub(super) sequence_bits: u8,
     pub(super) custom_epoch: u64,
+    pub(super) tick_ms: u64,
     pub(super) spin_enabled: bool,
     pub(super) spin_loops: u32,
     pub(super) spin_yield_every: u32,
+    pub(super) roles: Vec<NodeRole>,
 }
 
 impl SnowIDConfigBuilder {
     /// Create a new SnowIDConfigBuilder with default values
     pub fn new() -> Self {
         Self {
+            timestamp_bits: SnowID::TIMESTAMP_BITS as u8,
             node_bits: DEFAULT_NODE_BITS,
+            sequence_bits: SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - DEFAULT_NODE_BITS,
             custom_epoch: DEFAULT_CUSTOM_EPOCH,
+            tick_ms: DEFAULT_TICK_MS,
             spin_enabled: DEFAULT_SPIN_ENABLED,
             spin_loops: DEFAULT_SPIN_LOOPS,
             spin_yield_every: DEFAULT_SPIN_YIELD_EVERY,
+            roles: Vec::new(),
         }
     }
 
     /// Set the number of bits for node ID (6-16)
     /// Sequence bits will be automatically set to (22 - node_bits)
+    /// Resets the standard 64-bit layout with 1 ms ticks, overriding `js_safe`
     pub fn node_bits(mut self, bits: u8) -> Result<Self, SnowIDConfigError> {
         if !(6..=16).contains(&bits) {
             return Err(SnowIDConfigError::InvalidNodeBits { bits });
         }
+        self.timestamp_bits = SnowID::TIMESTAMP_BITS as u8;
         self.node_bits = bits;
+        self.sequence_bits = SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - bits;
+        self.tick_ms = DEFAULT_TICK_MS;
+        check_fits(&self.roles, self.max_node_id())?;
         Ok(self)
     }
 
+    /// Use a JavaScript-safe 53-bit layout so every ID is at most `Number.MAX_SAFE_INTEGER`
+    ///
+    /// Timestamp bits become `53 - node_bits - sequence_bits`, counted in ticks of `tick_ms`;
+    /// `tick_ms` may be at most `2^63 >> timestamp_bits` (about 49 days for 31 timestamp bits).
+    /// Check `SnowIDConfig::lifetime()` for how long the resulting layout lasts.
+    /// Overrides a previous `node_bits` call.
+    pub fn js_safe(
+        mut self,
+        tick_ms: u64,
+        node_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, SnowIDConfigError> {
+        if tick_ms == 0 {
+            return Err(SnowIDConfigError::TickTooSmall { tick_ms });
+        }
+        if !(1..=16).contains(&node_bits)
+            || !(1..=16).contains(&sequence_bits)
+            || node_bits + sequence_bits > SnowID::TOTAL_NODE_AND_SEQUENCE_BITS
+        {
+            return Err(SnowIDConfigError::UnsupportedJsSafeLayout {
+                node_bits,
+                sequence_bits,
+            });
+        }
+        let timestamp_bits = JS_SAFE_TOTAL_BITS - node_bits - sequence_bits;
+        // Keep the layout's lifetime within 2^63 ms so `timestamp_ms` cannot overflow
+        let max_tick_ms = (1u64 << 63) >> timestamp_bits;
+        if tick_ms > max_tick_ms {
+            return Err(SnowIDConfigError::TickTooLarge {
+                tick_ms,
+                max: max_tick_ms,
+            });
+        }
+        self.timestamp_bits = timestamp_bits;
+        self.node_bits = node_bits;
+        self.sequence_bits = sequence_bits;
+        self.tick_ms = tick_ms;
+        check_fits(&self.roles, self.max_node_id())?;
+        Ok(self)
+    }
+
+    /// Reserve a named range of node IDs, e.g. `.node_role("batch", 900..=999)`
+    ///
+    /// Roles must not overlap and must fit the node bits; once any role is declared,
+    /// generators reject node IDs outside every role. Names may come from runtime configuration.
+    pub fn node_role(
+        mut self,
+        name: impl Into<String>,
+        nodes: RangeInclusive<u16>,
+    ) -> Result<Self, SnowIDConfigError> {
+        let max = self.max_node_id();
+        insert_role(&mut self.roles, name.into(), nodes, max)?;
+        Ok(self)
+    }
+
+    fn max_node_id(&self) -> u16 {
+        SnowIDConfig::calculate_mask(self.node_bits)
+    }
+
     /// Set a custom epoch timestamp in milliseconds
     pub const fn epoch(mut self, epoch: u64) -> Self {
         self.custom_epoch = epoch;
@@ -1,23 +1,47 @@
 //! Configuration for SnowID generator
 
 mod builder;
+mod roles;
 
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 pub use builder::SnowIDConfigBuilder;
 use builder::{
     DEFAULT_CUSTOM_EPOCH, DEFAULT_NODE_BITS, DEFAULT_SPIN_ENABLED, DEFAULT_SPIN_LOOPS,
-    DEFAULT_SPIN_YIELD_EVERY,
+    DEFAULT_SPIN_YIELD_EVERY, DEFAULT_TICK_MS,
 };
+pub use roles::NodeRole;
+
+use roles::NodeRoles;
 
 use crate::SnowID;
+use crate::extractor::SnowIDExtractor;
 
 /// Errors related to `SnowIDConfig` builder validation
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum SnowIDConfigError {
     /// Provided node bits are out of the supported range [6, 16]
     InvalidNodeBits { bits: u8 },
+    /// JavaScript-safe layout needs 1-16 node bits, 1-16 sequence bits and at most 22 in total
+    UnsupportedJsSafeLayout { node_bits: u8, sequence_bits: u8 },
+    /// Tick duration must be at least 1 millisecond
+    TickTooSmall { tick_ms: u64 },
+    /// Tick duration is so long that timestamps in milliseconds would overflow
+    TickTooLarge { tick_ms: u64, max: u64 },
+    /// Role range is empty or exceeds the largest node ID of the layout
+    InvalidRoleRange {
+        name: String,
+        first: u16,
+        last: u16,
+        max: u16,
+    },
+    /// Role name was declared twice
+    DuplicateRole { name: String },
+    /// Role range overlaps the range of another role
+    OverlappingRoles { name: String, other: String },
 }
 
 impl fmt::Display for SnowIDConfigError {
@@ -26,6 +50,49 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             SnowIDConfigError::InvalidNodeBits { bits } => {
                 write!(f, "Node bits {} must be between 6 and 16", bits)
             }
+            SnowIDConfigError::UnsupportedJsSafeLayout {
+                node_bits,
+                sequence_bits,
+            } => {
+                write!(
+                    f,
+                    "JavaScript-safe layout with {} node bits and {} sequence bits is invalid \
+                     (each must be between 1 and 16, at most 22 combined)",
+                    node_bits, sequence_bits
+                )
+            }
+            SnowIDConfigError::TickTooSmall { tick_ms } => {
+                write!(
+                    f,
+                    "Tick of {} ms is invalid, must be at least 1 ms",
+                    tick_ms
+                )
+            }
+            SnowIDConfigError::TickTooLarge { tick_ms, max } => {
+                write!(
+                    f,
+                    "Tick of {} ms is invalid, must be at most {} ms for this layout",
+                    tick_ms, max
+                )
+            }
+            SnowIDConfigError::InvalidRoleRange {
+                name,
+                first,
+                last,
+                max,
+            } => {
+                write!(
+                    f,
+                    "Role '{}' range {}..={} is invalid, must be non-empty and end at most at {}",
+                    name, first, last, max
+                )
+            }
+            SnowIDConfigError::DuplicateRole { name } => {
+                write!(f, "Role '{}' is declared more than once", name)
+            }
+            SnowIDConfigError::OverlappingRoles { name, other } => {
+                write!(f, "Role '{}' overlaps role '{}'", name, other)
+            }
         }
     }
 }
@@ -33,12 +100,15 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 impl Error for SnowIDConfigError {}
 
 /// Configuration for SnowID generator
-/// Copy-optimized with const-evaluable fields
-#[derive(Debug, Clone, Copy)]
+/// Const-evaluable layout fields; declared node roles are shared, so clones are cheap
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct SnowIDConfig {
+    timestamp_bits: u8,
     node_bits: u8,
+    sequence_bits: u8,
     custom_epoch: u64,
+    tick_ms: u64,
     timestamp_shift: u8,
     node_shift: u8,
     timestamp_mask: u64,
@@ -47,9 +117,13 @@ pub struct SnowIDConfig {
     spin_enabled: bool,
     spin_loops: u32,
     spin_yield_every: u32,
+    roles: NodeRoles,
 }
 
 impl SnowIDConfig {
+    /// Largest integer a JavaScript `Number` represents exactly (2^53 - 1)
+    pub const MAX_SAFE_INTEGER: u64 = (1u64 << 53) - 1;
+
     /// Calculate mask for given number of bits
     #[inline]
     pub(crate) const fn calculate_mask(bits: u8) -> u16 {
@@ -59,26 +133,54 @@ pub(crate) const fn calculate_mask(bits: u8) -> u16 {
     /// Create new SnowIDConfig with given node bits
     fn new(node_bits: u8, custom_epoch: u64) -> Self {
         let sequence_bits = SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - node_bits;
+        Self::with_layout(
+            SnowID::TIMESTAMP_BITS as u8,
+            node_bits,
+            sequence_bits,
+            custom_epoch,
+            DEFAULT_TICK_MS,
+        )
+    }
+
+    /// Create new SnowIDConfig with an explicit bit layout and tick duration
+    fn with_layout(
+        timestamp_bits: u8,
+        node_bits: u8,
+        sequence_bits: u8,
+        custom_epoch: u64,
+        tick_ms: u64,
+    ) -> Self {
         Self {
+            timestamp_bits,
             node_bits,
+            sequence_bits,
             custom_epoch,
-            timestamp_shift: SnowID::TOTAL_NODE_AND_SEQUENCE_BITS,
+            tick_ms,
+            timestamp_shift: node_bits + sequence_bits,
             node_shift: sequence_bits,
-            timestamp_mask: (1u64 << SnowID::TIMESTAMP_BITS) - 1,
+            timestamp_mask: (1u64 << timestamp_bits) - 1,
             node_mask: Self::calculate_mask(node_bits),
             sequence_mask: Self::calculate_mask(sequence_bits),
             spin_enabled: DEFAULT_SPIN_ENABLED,
             spin_loops: DEFAULT_SPIN_LOOPS,
             spin_yield_every: DEFAULT_SPIN_YIELD_EVERY,
+            roles: NodeRoles::new(),
         }
     }
 
     /// Create config from builder
     pub(crate) fn from_builder(b: SnowIDConfigBuilder) -> Self {
-        let mut cfg = Self::new(b.node_bits, b.custom_epoch);
+        let mut cfg = Self::with_layout(
+            b.timestamp_bits,
+            b.node_bits,
+            b.sequence_bits,
+            b.custom_epoch,
+            b.tick_ms,
+        );
         cfg.spin_enabled = b.spin_enabled;
         cfg.spin_loops = b.spin_loops;
         cfg.spin_yield_every = b.spin_yield_every;
+        cfg.roles = NodeRoles::from_slice(&b.roles);
         cfg
     }
 
@@ -87,11 +189,27 @@ pub fn builder() -> SnowIDConfigBuilder {
         SnowIDConfigBuilder::new()
     }
 
+    /// Create a read-only extractor for decoding IDs of this layout
+    pub fn extractor(&self) -> SnowIDExtractor {
+        SnowIDExtractor::new(self.clone())
+    }
+
     #[inline(always)]
     pub const fn epoch(&self) -> u64 {
         self.custom_epoch
     }
 
+    /// Duration of one timestamp tick in milliseconds (1 unless a coarser tick is configured)
+    #[inline(always)]
+    pub const fn tick_ms(&self) -> u64 {
+        self.tick_ms
+    }
+
+    #[inline(always)]
+    pub const fn timestamp_bits(&self) -> u8 {
+        self.timestamp_bits
+    }
+
     #[inline(always)]
     pub const fn node_bits(&self) -> u8 {
         self.node_bits
@@ -99,7 +217,32 @@ pub const fn node_bits(&self) -> u8 {
 
     #[inline(always)]
     pub const fn sequence_bits(&self) -> u8 {
-        SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - self.node_bits
+        self.sequence_bits
+    }
+
+    /// Largest timestamp (in ticks since epoch) the layout can hold
+    #[inline(always)]
+    pub const fn max_timestamp(&self) -> u64 {
+        self.timestamp_mask
+    }
+
+    /// Largest ID value the layout can produce
+    #[inline(always)]
+    pub const fn max_id(&self) -> u64 {
+        (self.timestamp_mask << self.timestamp_shift)
+            | ((self.node_mask as u64) << self.node_shift)
+            | self.sequence_mask as u64
+    }
+
+    /// Whether every ID fits in `MAX_SAFE_INTEGER` and can be used as a JavaScript number
+    #[inline(always)]
+    pub const fn is_js_safe(&self) -> bool {
+        self.max_id() <= Self::MAX_SAFE_INTEGER
+    }
+
+    /// How long after the epoch the layout can keep generating IDs
+    pub const fn lifetime(&self) -> Duration {
+        Duration::from_millis((self.timestamp_mask + 1).saturating_mul(self.tick_ms))
     }
 
     #[inline(always)]
@@ -112,6 +255,30 @@ pub const fn max_sequence_id(&self) -> u16 {
         self.sequence_mask
     }
 
+    /// Node roles declared with `SnowIDConfigBuilder::node_role`, in declaration order
+    #[inline]
+    pub fn roles(&self) -> &[NodeRole] {
+        self.roles.as_slice()
+    }
+
+    /// Look up a declared role by name
+    #[inline]
+    pub fn role(&self, name: &str) -> Option<&NodeRole> {
+        self.roles.get(name)
+    }
+
+    /// Role whose range contains `node_id`
+    #[inline]
+    pub fn role_of(&self, node_id: u16) -> Option<&NodeRole> {
+        self.roles.of_node(node_id)
+    }
+
+    /// Whether `node_id` may be used: always with no roles, otherwise only inside a role
+    #[inline]
+    pub(crate) fn node_in_roles(&self, node_id: u16) -> bool {
+        self.roles.is_empty() || self.roles.of_node(node_id).is_some()
+    }
+
     #[inline(always)]
     pub const fn spin_enabled(&self) -> bool {
         self.spin_enabled
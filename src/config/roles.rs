@@ -0,0 +1,131 @@
+//! Named node ID ranges ("roles") declared on a configuration
+
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use super::SnowIDConfigError;
+
+/// A named, inclusive range of node IDs reserved for one purpose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRole {
+    /// Role name, e.g. `"services"`
+    pub name: String,
+    /// First node ID of the role
+    pub first: u16,
+    /// Last node ID of the role (inclusive)
+    pub last: u16,
+}
+
+impl NodeRole {
+    /// Whether `node_id` belongs to the role
+    #[inline]
+    pub const fn contains(&self, node_id: u16) -> bool {
+        self.first <= node_id && node_id <= self.last
+    }
+
+    /// Node IDs of the role
+    #[inline]
+    pub const fn nodes(&self) -> RangeInclusive<u16> {
+        self.first..=self.last
+    }
+
+    /// Number of node IDs in the role
+    #[inline]
+    pub const fn size(&self) -> u32 {
+        (self.last - self.first) as u32 + 1
+    }
+
+    /// Node ID at `index` within the role
+    #[inline]
+    pub const fn node(&self, index: u16) -> Option<u16> {
+        if (index as u32) < self.size() {
+            Some(self.first + index)
+        } else {
+            None
+        }
+    }
+}
+
+/// Shared, immutable role table; cloning a configuration only bumps the reference count
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NodeRoles(Option<Arc<[NodeRole]>>);
+
+impl NodeRoles {
+    pub(crate) const fn new() -> Self {
+        Self(None)
+    }
+
+    pub(crate) fn from_slice(roles: &[NodeRole]) -> Self {
+        if roles.is_empty() {
+            return Self::new();
+        }
+        Self(Some(roles.into()))
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[NodeRole] {
+        self.0.as_deref().unwrap_or(&[])
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&NodeRole> {
+        self.as_slice().iter().find(|role| role.name == name)
+    }
+
+    pub(crate) fn of_node(&self, node_id: u16) -> Option<&NodeRole> {
+        self.as_slice().iter().find(|role| role.contains(node_id))
+    }
+}
+
+/// Add a role to a builder's list, rejecting duplicates, overlaps and ranges beyond `max_node_id`
+pub(crate) fn insert_role(
+    roles: &mut Vec<NodeRole>,
+    name: String,
+    nodes: RangeInclusive<u16>,
+    max_node_id: u16,
+) -> Result<(), SnowIDConfigError> {
+    let role = NodeRole {
+        name,
+        first: *nodes.start(),
+        last: *nodes.end(),
+    };
+    check_range(&role, max_node_id)?;
+    if roles.iter().any(|other| other.name == role.name) {
+        return Err(SnowIDConfigError::DuplicateRole { name: role.name });
+    }
+    if let Some(other) = roles
+        .iter()
+        .find(|other| role.first <= other.last && other.first <= role.last)
+    {
+        return Err(SnowIDConfigError::OverlappingRoles {
+            name: role.name,
+            other: other.name.clone(),
+        });
+    }
+
+    roles.push(role);
+    Ok(())
+}
+
+/// Check every role still fits after the node bits changed
+pub(crate) fn check_fits(roles: &[NodeRole], max_node_id: u16) -> Result<(), SnowIDConfigError> {
+    roles
+        .iter()
+        .try_for_each(|role| check_range(role, max_node_id))
+}
+
+fn check_range(role: &NodeRole, max_node_id: u16) -> Result<(), SnowIDConfigError> {
+    if role.first > role.last || role.last > max_node_id {
+        return Err(SnowIDConfigError::InvalidRoleRange {
+            name: role.name.clone(),
+            first: role.first,
+            last: role.last,
+            max: max_node_id,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! Typo-tolerant Crockford base32 codec with optional check symbol
+//!
+//! Designed for IDs read aloud or typed by hand:
+//! - Decoding is case-insensitive
+//! - `I` and `L` decode as `1`, `O` decodes as `0`
+//! - Hyphens are ignored, so `3KT-9QW-0Z` equals `3KT9QW0Z`
+//! - An optional mod-37 check symbol catches single-character typos and most transpositions
+
+use crate::base62::DecodeError;
+use crate::encoding::Encoding;
+
+/// Maximum encoded length of a u64 without check symbol (13 characters)
+pub const MAX_LEN: usize = 13;
+
+/// Maximum encoded length of a u64 with check symbol (14 characters)
+pub const MAX_LEN_WITH_CHECK: usize = MAX_LEN + 1;
+
+/// Check symbols: the 32 digit symbols followed by `*`, `~`, `$`, `=` and `U`
+const CHECK_SYMBOLS: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+/// Modulus of the check symbol
+const CHECK_MODULUS: u64 = 37;
+
+/// Zero-allocation encoding to a fixed-size array
+/// Returns the array and the actual length of encoded bytes
+#[inline]
+pub fn encode_array(id: u64) -> ([u8; MAX_LEN_WITH_CHECK], usize) {
+    let (digits, len) = Encoding::CROCKFORD32.encode_array(id);
+    let mut buf = [0u8; MAX_LEN_WITH_CHECK];
+    buf[..len].copy_from_slice(&digits[..len]);
+    (buf, len)
+}
+
+/// Zero-allocation encoding with trailing check symbol to a fixed-size array
+/// Returns the array and the actual length of encoded bytes
+#[inline]
+pub fn encode_with_check_array(id: u64) -> ([u8; MAX_LEN_WITH_CHECK], usize) {
+    let (mut buf, len) = encode_array(id);
+    buf[len] = check_symbol(id);
+    (buf, len + 1)
+}
+
+/// Zero-allocation encoding into caller-provided buffer
+#[inline]
+pub fn encode_into(id: u64, buf: &mut [u8; MAX_LEN_WITH_CHECK]) -> &str {
+    let len;
+    (*buf, len) = encode_array(id);
+    // Crockford output is always valid ASCII
+    std::str::from_utf8(&buf[..len]).unwrap()
+}
+
+/// Zero-allocation encoding with check symbol into caller-provided buffer
+#[inline]
+pub fn encode_with_check_into(id: u64, buf: &mut [u8; MAX_LEN_WITH_CHECK]) -> &str {
+    let len;
+    (*buf, len) = encode_with_check_array(id);
+    // Crockford output is always valid ASCII
+    std::str::from_utf8(&buf[..len]).unwrap()
+}
+
+/// Crockford base32 encode with String allocation (convenience wrapper)
+#[inline]
+pub fn encode(id: u64) -> String {
+    let mut buf = [0u8; MAX_LEN_WITH_CHECK];
+    encode_into(id, &mut buf).to_owned()
+}
+
+/// Crockford base32 encode with trailing check symbol (convenience wrapper)
+#[inline]
+pub fn encode_with_check(id: u64) -> String {
+    let mut buf = [0u8; MAX_LEN_WITH_CHECK];
+    encode_with_check_into(id, &mut buf).to_owned()
+}
+
+/// Decode a Crockford base32 string without check symbol, tolerating common typos
+pub fn decode(encoded: &str) -> Result<u64, DecodeError> {
+    decode_digits(encoded.as_bytes())
+}
+
+/// Decode a Crockford base32 string whose last symbol is the check symbol
+/// A mistyped ID fails with `ChecksumMismatch` instead of resolving to another value
+pub fn decode_with_check(encoded: &str) -> Result<u64, DecodeError> {
+    let bytes = trim_trailing_hyphens(encoded.as_bytes());
+    let Some((&check, digits)) = bytes.split_last() else {
+        return Err(DecodeError::Empty);
+    };
+
+    let value = decode_digits(digits)?;
+    let expected = check_value(check).ok_or(DecodeError::InvalidCharacter {
+        byte: check,
+        position: digits.len(),
+    })?;
+    if value % CHECK_MODULUS != expected {
+        return Err(DecodeError::ChecksumMismatch);
+    }
+    Ok(value)
+}
+
+/// Compute the check symbol for a value
+#[inline]
+pub fn check_symbol(id: u64) -> u8 {
+    CHECK_SYMBOLS[(id % CHECK_MODULUS) as usize]
+}
+
+fn decode_digits(bytes: &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0u64;
+    let mut digits = 0usize;
+    for (position, &byte) in bytes.iter().enumerate() {
+        if byte == b'-' {
+            continue;
+        }
+        let digit = digit_value(byte).ok_or(DecodeError::InvalidCharacter { byte, position })?;
+        value = value
+            .checked_mul(32)
+            .and_then(|v| v.checked_add(digit))
+            .ok_or(DecodeError::Overflow)?;
+        digits += 1;
+    }
+    if digits == 0 {
+        return Err(DecodeError::Empty);
+    }
+    Ok(value)
+}
+
+fn trim_trailing_hyphens(mut bytes: &[u8]) -> &[u8] {
+    while let [rest @ .., b'-'] = bytes {
+        bytes = rest;
+    }
+    bytes
+}
+
+/// Map a symbol to its digit value, case-insensitive with I/L -> 1 and O -> 0
+#[inline]
+fn digit_value(c: u8) -> Option<u64> {
+    let value = match c.to_ascii_uppercase() {
+        c @ b'0'..=b'9' => c - b'0',
+        b'O' => 0,
+        b'I' | b'L' => 1,
+        b'U' => return None,
+        c @ b'A'..=b'Z' => {
+            // Skip the excluded letters I, L, O (U is rejected above)
+            let mut v = c - b'A' + 10;
+            if c > b'I' {
+                v -= 1;
+            }
+            if c > b'L' {
+                v -= 1;
+            }
+            if c > b'O' {
+                v -= 1;
+            }
+            if c > b'U' {
+                v -= 1;
+            }
+            v
+        }
+        _ => return None,
+    };
+    Some(value as u64)
+}
+
+/// Map a check symbol to its value (0-36)
+#[inline]
+fn check_value(c: u8) -> Option<u64> {
+    match c.to_ascii_uppercase() {
+        b'*' => Some(32),
+        b'~' => Some(33),
+        b'$' => Some(34),
+        b'=' => Some(35),
+        b'U' => Some(36),
+        c => digit_value(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        for id in [0u64, 1, 31, 32, 36, 37, 151819733950271234, u64::MAX] {
+            assert_eq!(decode(&encode(id)).unwrap(), id);
+            assert_eq!(decode_with_check(&encode_with_check(id)).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_digit_values_match_alphabet() {
+        for (i, &c) in Encoding::CROCKFORD32.alphabet().iter().enumerate() {
+            assert_eq!(digit_value(c), Some(i as u64));
+            assert_eq!(digit_value(c.to_ascii_lowercase()), Some(i as u64));
+        }
+    }
+
+    #[test]
+    fn test_typo_tolerance() {
+        let id = 151819733950271234u64;
+        let encoded = encode(id);
+        assert_eq!(decode(&encoded.to_lowercase()).unwrap(), id);
+
+        assert_eq!(decode("1O").unwrap(), 32);
+        assert_eq!(decode("Io").unwrap(), 32);
+        assert_eq!(decode("l0").unwrap(), 32);
+        assert_eq!(decode("3KT-9QW-0Z").unwrap(), decode("3KT9QW0Z").unwrap());
+    }
+
+    #[test]
+    fn test_check_symbols() {
+        assert_eq!(encode_with_check(32), "10*");
+        assert_eq!(encode_with_check(36), "14U");
+        assert_eq!(decode_with_check("14u").unwrap(), 36);
+        assert_eq!(decode_with_check("1-0-*").unwrap(), 32);
+    }
+
+    #[test]
+    fn test_check_detects_typos() {
+        let id = 151819733950271234u64;
+        let encoded = encode_with_check(id);
+        let bytes = encoded.as_bytes();
+
+        // Every single-character substitution is detected
+        for pos in 0..bytes.len() - 1 {
+            for &c in Encoding::CROCKFORD32.alphabet() {
+                if c == bytes[pos] {
+                    continue;
+                }
+                let mut typo = bytes.to_vec();
+                typo[pos] = c;
+                let typo = String::from_utf8(typo).unwrap();
+                assert!(decode_with_check(&typo).is_err(), "{typo} should fail");
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            decode("1-U"),
+            Err(DecodeError::InvalidCharacter {
+                byte: b'U',
+                position: 2
+            })
+        );
+        assert!(matches!(
+            decode("*"),
+            Err(DecodeError::InvalidCharacter { byte: b'*', .. })
+        ));
+        assert_eq!(
+            decode_with_check("10!"),
+            Err(DecodeError::InvalidCharacter {
+                byte: b'!',
+                position: 2
+            })
+        );
+        assert_eq!(decode("---"), Err(DecodeError::Empty));
+        assert_eq!(decode_with_check("0"), Err(DecodeError::Empty));
+        assert!(matches!(
+            decode("ZZZZZZZZZZZZZ"),
+            Err(DecodeError::Overflow)
+        ));
+        assert!(matches!(
+            decode_with_check("101"),
+            Err(DecodeError::ChecksumMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_array_and_into_match_string() {
+        let id = 98765432109876u64;
+        let (arr, len) = encode_with_check_array(id);
+        let mut buf = [0u8; MAX_LEN_WITH_CHECK];
+        assert_eq!(&arr[..len], encode_with_check(id).as_bytes());
+        assert_eq!(encode_into(id, &mut buf), encode(id));
+    }
+}
@@ -0,0 +1,298 @@
+//! Selectable alphabets for encoding SnowID values
+//!
+//! Built-in encodings:
+//! - `BASE62`: 0-9A-Za-z, same output as `crate::base62`
+//! - `BASE62_ALT`: 0-9a-zA-Z, the alternative base62 ordering
+//! - `BASE58`: Bitcoin alphabet without look-alike characters (0, O, I, l)
+//! - `CROCKFORD32`: Crockford base32 (uppercase, no I, L, O, U)
+//! - `BASE36`: 0-9a-z, case-sensitive lowercase
+//! - `HEX`: Lowercase hexadecimal
+//!
+//! Every encoding offers the same zero-allocation shape as `crate::base62`:
+//! `encode_array`, `encode_into` and the allocating `encode`.
+
+use crate::base62::DecodeError;
+
+/// Buffer size that fits a u64 in any supported alphabet (16 hex digits)
+pub const MAX_LEN: usize = 16;
+
+/// Marker for bytes outside the alphabet in the decode table
+const INVALID: u8 = u8::MAX;
+
+/// An alphabet for encoding u64 values as strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    alphabet: &'static [u8],
+    decode_table: [u8; 256],
+    max_len: usize,
+}
+
+impl Encoding {
+    /// Base62 with digits, uppercase, then lowercase (matches `crate::base62`)
+    pub const BASE62: Self =
+        Self::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+    /// Base62 with digits, lowercase, then uppercase
+    pub const BASE62_ALT: Self =
+        Self::new(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+    /// Bitcoin base58 alphabet, without 0, O, I and l
+    pub const BASE58: Self =
+        Self::new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+    /// Crockford base32 alphabet, without I, L, O and U
+    pub const CROCKFORD32: Self = Self::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+
+    /// Base36 with digits and lowercase letters
+    pub const BASE36: Self = Self::new(b"0123456789abcdefghijklmnopqrstuvwxyz");
+
+    /// Lowercase hexadecimal
+    pub const HEX: Self = Self::new(b"0123456789abcdef");
+
+    /// Create an encoding from a custom alphabet of 16 to 64 unique printable ASCII characters
+    /// (at least 16 so any u64 fits in `MAX_LEN`)
+    ///
+    /// # Panics
+    /// Panics (at compile time in const context) on an invalid alphabet
+    pub const fn new(alphabet: &'static [u8]) -> Self {
+        assert!(
+            alphabet.len() >= 16 && alphabet.len() <= 64,
+            "alphabet must have 16 to 64 characters"
+        );
+
+        let mut decode_table = [INVALID; 256];
+        let mut i = 0;
+        while i < alphabet.len() {
+            let c = alphabet[i];
+            assert!(c.is_ascii_graphic(), "alphabet must be printable ASCII");
+            assert!(
+                decode_table[c as usize] == INVALID,
+                "alphabet must not repeat characters"
+            );
+            decode_table[c as usize] = i as u8;
+            i += 1;
+        }
+
+        Self {
+            alphabet,
+            decode_table,
+            max_len: Self::digits_for_max(alphabet.len() as u64),
+        }
+    }
+
+    /// Number of digits needed to encode u64::MAX in the given base
+    const fn digits_for_max(base: u64) -> usize {
+        let mut n = u64::MAX;
+        let mut len = 0;
+        while n > 0 {
+            n /= base;
+            len += 1;
+        }
+        len
+    }
+
+    /// Number of characters in the alphabet
+    #[inline(always)]
+    pub const fn base(&self) -> u64 {
+        self.alphabet.len() as u64
+    }
+
+    /// Maximum encoded length of a u64 in this alphabet
+    #[inline(always)]
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// The alphabet characters in digit order
+    #[inline(always)]
+    pub const fn alphabet(&self) -> &'static [u8] {
+        self.alphabet
+    }
+
+    /// Zero-allocation encoding to a fixed-size array
+    /// Returns the array and the actual length of encoded bytes
+    #[inline]
+    pub fn encode_array(&self, id: u64) -> ([u8; MAX_LEN], usize) {
+        let mut digits = [0u8; MAX_LEN];
+        let base = self.base();
+        let mut n = id;
+        let mut pos = MAX_LEN;
+        loop {
+            pos -= 1;
+            digits[pos] = self.alphabet[(n % base) as usize];
+            n /= base;
+            if n == 0 {
+                break;
+            }
+        }
+
+        let len = MAX_LEN - pos;
+        let mut buf = [0u8; MAX_LEN];
+        buf[..len].copy_from_slice(&digits[pos..]);
+        (buf, len)
+    }
+
+    /// Zero-allocation encoding into caller-provided buffer
+    /// Returns a str slice of the encoded portion
+    #[inline]
+    pub fn encode_into<'a>(&self, id: u64, buf: &'a mut [u8; MAX_LEN]) -> &'a str {
+        let len;
+        (*buf, len) = self.encode_array(id);
+        // Alphabets are always valid ASCII
+        std::str::from_utf8(&buf[..len]).unwrap()
+    }
+
+    /// Encode with String allocation (convenience wrapper)
+    /// For hot paths, prefer `encode_array` or `encode_into`
+    #[inline]
+    pub fn encode(&self, id: u64) -> String {
+        let (buf, len) = self.encode_array(id);
+        // Alphabets are always valid ASCII
+        std::str::from_utf8(&buf[..len]).unwrap().to_owned()
+    }
+
+    /// Decode a string in this alphabet to a u64, handling potential overflow
+    pub fn decode(&self, encoded: &str) -> Result<u64, DecodeError> {
+        let bytes = encoded.as_bytes();
+        if bytes.is_empty() {
+            return Err(DecodeError::Empty);
+        }
+        if bytes.len() > self.max_len {
+            return Err(DecodeError::TooLong { len: bytes.len() });
+        }
+
+        let base = self.base();
+        let mut value = 0u64;
+        for (position, &byte) in bytes.iter().enumerate() {
+            let digit = self.decode_table[byte as usize];
+            if digit == INVALID {
+                return Err(DecodeError::InvalidCharacter { byte, position });
+            }
+            value = value
+                .checked_mul(base)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or(DecodeError::Overflow)?;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Encoding; 6] = [
+        Encoding::BASE62,
+        Encoding::BASE62_ALT,
+        Encoding::BASE58,
+        Encoding::CROCKFORD32,
+        Encoding::BASE36,
+        Encoding::HEX,
+    ];
+
+    #[test]
+    fn test_roundtrip_all_encodings() {
+        let values = [0u64, 1, 57, 58, 1234567890, 151819733950271234, u64::MAX];
+        for encoding in ALL {
+            for &value in &values {
+                let encoded = encoding.encode(value);
+                assert!(encoded.len() <= encoding.max_len());
+                assert_eq!(encoding.decode(&encoded).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_len() {
+        assert_eq!(Encoding::BASE62.max_len(), 11);
+        assert_eq!(Encoding::BASE58.max_len(), 11);
+        assert_eq!(Encoding::CROCKFORD32.max_len(), 13);
+        assert_eq!(Encoding::BASE36.max_len(), 13);
+        assert_eq!(Encoding::HEX.max_len(), 16);
+        for encoding in ALL {
+            assert_eq!(encoding.encode(u64::MAX).len(), encoding.max_len());
+        }
+    }
+
+    #[test]
+    fn test_base62_matches_base62_module() {
+        for id in [0u64, 61, 62, 151819733950271234, u64::MAX] {
+            assert_eq!(Encoding::BASE62.encode(id), crate::base62::encode(id));
+        }
+        assert_eq!(Encoding::BASE62.encode(10), "A");
+        assert_eq!(Encoding::BASE62_ALT.encode(10), "a");
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(Encoding::HEX.encode(0xdeadbeef), "deadbeef");
+        assert_eq!(Encoding::HEX.encode(u64::MAX), format!("{:x}", u64::MAX));
+        assert_eq!(Encoding::BASE36.encode(35), "z");
+        assert_eq!(Encoding::CROCKFORD32.encode(31), "Z");
+        assert_eq!(Encoding::CROCKFORD32.encode(18), "J");
+        assert_eq!(Encoding::BASE58.encode(0), "1");
+        assert_eq!(Encoding::BASE58.encode(57), "z");
+    }
+
+    #[test]
+    fn test_array_and_into_match_string() {
+        let id = 98765432109876u64;
+        for encoding in ALL {
+            let (arr, len) = encoding.encode_array(id);
+            let mut buf = [0u8; MAX_LEN];
+            let into = encoding.encode_into(id, &mut buf);
+            assert_eq!(
+                std::str::from_utf8(&arr[..len]).unwrap(),
+                encoding.encode(id)
+            );
+            assert_eq!(into, encoding.encode(id));
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            Encoding::BASE58.decode("1O"),
+            Err(DecodeError::InvalidCharacter {
+                byte: b'O',
+                position: 1
+            })
+        );
+        assert_eq!(
+            Encoding::HEX.decode("DEAD"),
+            Err(DecodeError::InvalidCharacter {
+                byte: b'D',
+                position: 0
+            })
+        );
+        assert_eq!(
+            Encoding::HEX.decode("10000000000000000"),
+            Err(DecodeError::TooLong { len: 17 })
+        );
+        assert_eq!(
+            Encoding::BASE58.decode("zzzzzzzzzzz"),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(Encoding::BASE36.decode(""), Err(DecodeError::Empty));
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        const UPPER_HEX: Encoding = Encoding::new(b"0123456789ABCDEF");
+        assert_eq!(UPPER_HEX.encode(0xdeadbeef), "DEADBEEF");
+        assert_eq!(UPPER_HEX.max_len(), 16);
+        assert_eq!(UPPER_HEX.decode("DEADBEEF").unwrap(), 0xdeadbeef);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not repeat")]
+    fn test_duplicate_alphabet_panics() {
+        Encoding::new(b"0123456789abcdea");
+    }
+
+    #[test]
+    #[should_panic(expected = "16 to 64 characters")]
+    fn test_short_alphabet_panics() {
+        Encoding::new(b"01");
+    }
+}
@@ -2,10 +2,21 @@
 use std::fmt;
 
 /// Represents errors that can occur during SnowID operations
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum SnowIDError {
     /// Error when node ID exceeds the maximum allowed value
     InvalidNodeId { node_id: u16, max: u16 },
+    /// Error when the current timestamp no longer fits the configured layout
+    TimestampOverflow { timestamp: u64, max: u64 },
+    /// Error when a tracked generator for the same node and layout is still alive
+    DuplicateGenerator { node_id: u16 },
+    /// Error when roles are declared and the node ID belongs to none of them
+    NodeOutsideRoles { node_id: u16 },
+    /// Error when no role with this name is declared
+    UnknownRole { role: String },
+    /// Error when the index exceeds the number of node IDs in the role
+    RoleIndexOutOfRange { role: String, index: u16, size: u32 },
 }
 
 impl fmt::Display for SnowIDError {
@@ -18,6 +29,31 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     node_id, max
                 )
             }
+            SnowIDError::TimestampOverflow { timestamp, max } => {
+                write!(
+                    f,
+                    "Timestamp {} exceeds the layout maximum of {}, no more IDs can be generated",
+                    timestamp, max
+                )
+            }
+            SnowIDError::DuplicateGenerator { node_id } => {
+                write!(
+                    f,
+                    "A generator for node {} with the same layout is already running",
+                    node_id
+                )
+            }
+            SnowIDError::NodeOutsideRoles { node_id } => {
+                write!(f, "Node ID {} is outside every declared role", node_id)
+            }
+            SnowIDError::UnknownRole { role } => write!(f, "Role '{}' is not declared", role),
+            SnowIDError::RoleIndexOutOfRange { role, index, size } => {
+                write!(
+                    f,
+                    "Index {} is out of range for role '{}' with {} node IDs",
+                    index, role, size
+                )
+            }
         }
     }
 }
@@ -40,6 +76,44 @@ fn test_error_display() {
         );
     }
 
+    #[test]
+    fn test_timestamp_overflow_display() {
+        let overflow = SnowIDError::TimestampOverflow {
+            timestamp: 2048,
+            max: 2047,
+        };
+        assert_eq!(
+            overflow.to_string(),
+            "Timestamp 2048 exceeds the layout maximum of 2047, no more IDs can be generated"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_generator_display() {
+        let duplicate = SnowIDError::DuplicateGenerator { node_id: 7 };
+        assert_eq!(
+            duplicate.to_string(),
+            "A generator for node 7 with the same layout is already running"
+        );
+    }
+
+    #[test]
+    fn test_role_errors_display() {
+        assert_eq!(
+            SnowIDError::NodeOutsideRoles { node_id: 950 }.to_string(),
+            "Node ID 950 is outside every declared role"
+        );
+        assert_eq!(
+            SnowIDError::RoleIndexOutOfRange {
+                role: "batch".to_owned(),
+                index: 100,
+                size: 100
+            }
+            .to_string(),
+            "Index 100 is out of range for role 'batch' with 100 node IDs"
+        );
+    }
+
     #[test]
     fn test_error_debug() {
         let invalid_node = SnowIDError::InvalidNodeId {
@@ -0,0 +1,165 @@
+//! Human-readable breakdown of a single SnowID
+//!
+//! Produced by `SnowIDExtractor::explain` for debugging and tooling.
+
+use std::fmt;
+
+use crate::base62;
+use crate::config::SnowIDConfig;
+
+/// All representations and components of one SnowID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnowIDExplanation {
+    /// Raw 64-bit value
+    pub raw: u64,
+    /// Zero-padded hexadecimal form, e.g. `0x021b5f3e60000f02`
+    pub hex: String,
+    /// Binary form split into `timestamp node sequence` fields
+    pub binary: String,
+    /// Base62 encoded form
+    pub base62: String,
+    /// Timestamp component in ticks since the configured epoch
+    pub timestamp: u64,
+    /// Timestamp as Unix milliseconds (tick and epoch applied)
+    pub unix_ms: u64,
+    /// Timestamp as an RFC 3339 UTC datetime, e.g. `2025-02-20T08:15:30.123Z`
+    pub datetime: String,
+    /// Node component
+    pub node: u16,
+    /// Name of the declared role containing the node, if any
+    pub role: Option<String>,
+    /// Sequence component
+    pub sequence: u16,
+}
+
+impl SnowIDExplanation {
+    pub(crate) fn new(id: u64, config: &SnowIDConfig) -> Self {
+        let timestamp = (id >> config.timestamp_shift()) & config.timestamp_mask();
+        let node = ((id >> config.node_shift()) & config.node_mask() as u64) as u16;
+        let sequence = (id & config.sequence_mask() as u64) as u16;
+        let unix_ms = timestamp
+            .saturating_mul(config.tick_ms())
+            .saturating_add(config.epoch());
+
+        Self {
+            raw: id,
+            hex: format!("{id:#018x}"),
+            binary: format!(
+                "{:0tw$b} {:0nw$b} {:0sw$b}",
+                timestamp,
+                node,
+                sequence,
+                tw = config.timestamp_bits() as usize,
+                nw = config.node_bits() as usize,
+                sw = config.sequence_bits() as usize,
+            ),
+            base62: base62::encode(id),
+            timestamp,
+            unix_ms,
+            datetime: format_utc_millis(unix_ms),
+            node,
+            role: config.role_of(node).map(|role| role.name.clone()),
+            sequence,
+        }
+    }
+}
+
+impl fmt::Display for SnowIDExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "SnowID {}", self.raw)?;
+        writeln!(f, "  hex:       {}", self.hex)?;
+        writeln!(f, "  binary:    {}", self.binary)?;
+        writeln!(f, "  base62:    {}", self.base62)?;
+        writeln!(
+            f,
+            "  timestamp: {} (unix ms {})",
+            self.timestamp, self.unix_ms
+        )?;
+        writeln!(f, "  datetime:  {}", self.datetime)?;
+        match &self.role {
+            Some(role) => writeln!(f, "  node:      {} ({})", self.node, role)?,
+            None => writeln!(f, "  node:      {}", self.node)?,
+        }
+        write!(f, "  sequence:  {}", self.sequence)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for SnowIDExplanation {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use ::serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("SnowIDExplanation", 10)?;
+        s.serialize_field("raw", &self.raw)?;
+        s.serialize_field("hex", &self.hex)?;
+        s.serialize_field("binary", &self.binary)?;
+        s.serialize_field("base62", &self.base62)?;
+        s.serialize_field("timestamp", &self.timestamp)?;
+        s.serialize_field("unix_ms", &self.unix_ms)?;
+        s.serialize_field("datetime", &self.datetime)?;
+        s.serialize_field("node", &self.node)?;
+        s.serialize_field("role", &self.role)?;
+        s.serialize_field("sequence", &self.sequence)?;
+        s.end()
+    }
+}
+
+/// Format Unix milliseconds as an RFC 3339 UTC datetime with millisecond precision
+fn format_utc_millis(unix_ms: u64) -> String {
+    let secs = unix_ms / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let rem = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        unix_ms % 1000
+    )
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the era start to 0000-03-01 so leap days fall at the end of each year
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, SecondsFormat, Utc};
+
+    #[test]
+    fn test_format_utc_millis() {
+        assert_eq!(format_utc_millis(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_utc_millis(1704067200000), "2024-01-01T00:00:00.000Z");
+        assert_eq!(format_utc_millis(1709210096789), "2024-02-29T12:34:56.789Z");
+    }
+
+    #[test]
+    fn test_format_utc_millis_matches_chrono() {
+        for ms in [
+            951782400000u64,
+            4102444799999,
+            1740039330123,
+            253402300799999,
+        ] {
+            let expected = DateTime::<Utc>::from_timestamp_millis(ms as i64)
+                .unwrap()
+                .to_rfc3339_opts(SecondsFormat::Millis, true);
+            assert_eq!(format_utc_millis(ms), expected);
+        }
+    }
+}
@@ -1,29 +1,59 @@
 use crate::config::SnowIDConfig;
+use crate::explain::SnowIDExplanation;
+use crate::generator::unix_time_ms;
+use crate::validation::{ValidationError, ValidationPolicy};
 
 /// SnowID component extractor
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct SnowIDExtractor {
     config: SnowIDConfig,
 }
 
 impl SnowIDExtractor {
     /// Create a new SnowID extractor with the given configuration
-    pub(crate) fn new(config: SnowIDConfig) -> Self {
+    /// Decodes IDs without needing a generator or node ID
+    pub const fn new(config: SnowIDConfig) -> Self {
         Self { config }
     }
 
+    /// Configuration used to decode IDs
+    #[inline(always)]
+    pub const fn config(&self) -> &SnowIDConfig {
+        &self.config
+    }
+
     /// Extract timestamp component from a SnowID
     #[inline(always)]
     pub fn timestamp(&self, id: u64) -> u64 {
         (id >> self.config.timestamp_shift()) & self.config.timestamp_mask()
     }
 
+    /// Extract timestamp as Unix milliseconds (tick and epoch applied)
+    ///
+    /// Saturates at `u64::MAX` when a far-future custom epoch would overflow
+    #[inline(always)]
+    pub fn timestamp_ms(&self, id: u64) -> u64 {
+        self.timestamp(id)
+            .saturating_mul(self.config.tick_ms())
+            .saturating_add(self.config.epoch())
+    }
+
     /// Extract node component from a SnowID
     #[inline(always)]
     pub fn node(&self, id: u64) -> u16 {
         ((id >> self.config.node_shift()) & self.config.node_mask() as u64) as u16
     }
 
+    /// Name of the declared role whose node range issued the ID
+    ///
+    /// `None` if the configuration declares no roles or the node lies outside all of them
+    #[inline]
+    pub fn role(&self, id: u64) -> Option<&str> {
+        self.config
+            .role_of(self.node(id))
+            .map(|role| role.name.as_str())
+    }
+
     /// Extract sequence component from a SnowID
     #[inline(always)]
     pub fn sequence(&self, id: u64) -> u16 {
@@ -39,6 +69,60 @@ pub fn decompose(&self, id: u64) -> (u64, u16, u16) {
         let sequence = (id & self.config.sequence_mask() as u64) as u16;
         (timestamp, node, sequence)
     }
+
+    /// Explain a SnowID: raw, hex, binary split, base62, timestamp, UTC datetime, node and sequence
+    /// Intended for debugging; `Display` prints a multi-line summary
+    pub fn explain(&self, id: u64) -> SnowIDExplanation {
+        SnowIDExplanation::new(id, &self.config)
+    }
+
+    /// Check that an untrusted ID is plausible under the given policy
+    /// Returns the first failed check as a structured reason
+    pub fn validate(&self, id: u64, policy: &ValidationPolicy) -> Result<(), ValidationError> {
+        self.validate_at(id, policy, unix_time_ms())
+    }
+
+    /// Like `validate`, with "now" given as Unix milliseconds
+    pub fn validate_at(
+        &self,
+        id: u64,
+        policy: &ValidationPolicy,
+        now_unix_ms: u64,
+    ) -> Result<(), ValidationError> {
+        let max_id = self.config.max_id();
+        if id > max_id {
+            return Err(ValidationError::ReservedBitsSet { id, max_id });
+        }
+
+        let (timestamp, node, _) = self.decompose(id);
+        if timestamp == 0 {
+            return Err(ValidationError::TimestampAtEpoch);
+        }
+
+        let unix_ms = self.timestamp_ms(id);
+        if let Some(not_before_ms) = policy.not_before_ms
+            && unix_ms < not_before_ms
+        {
+            return Err(ValidationError::TooOld {
+                unix_ms,
+                not_before_ms,
+            });
+        }
+
+        let max_unix_ms = now_unix_ms.saturating_add(policy.max_future.as_millis() as u64);
+        if unix_ms > max_unix_ms {
+            return Err(ValidationError::TooFarInFuture {
+                unix_ms,
+                max_unix_ms,
+            });
+        }
+
+        if !policy.nodes.allows(node) {
+            return Err(ValidationError::NodeNotAllowed { node });
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -74,7 +158,7 @@ fn test_decompose() {
     #[test]
     fn test_component_boundaries() {
         let config = SnowIDConfig::default();
-        let snowid_gen = SnowID::with_config(1, config).unwrap();
+        let snowid_gen = SnowID::with_config(1, config.clone()).unwrap();
 
         // Test maximum values
         let max_timestamp = (1u64 << 42) - 1;
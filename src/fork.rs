@@ -0,0 +1,349 @@
+//! Fork-safety for generators
+//!
+//! After `fork()` parent and child hold identical copies of a generator's state and node ID,
+//! so both issue the same IDs. `ForkSafeSnowID` records the process ID it was created in and
+//! compares it whenever the generator claims a new tick (including after a sequence rollover).
+//! In a forked child it either refuses to generate with `ForkError::Detected`, or takes a new
+//! node ID from a configured `NodeIdProvider`.
+//!
+//! IDs within the tick in progress at the moment of the fork are not checked. Call
+//! `check_fork` in the child right after forking (an after-fork hook) to close that window.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::error::SnowIDError;
+use crate::node::{NodeIdError, NodeIdProvider};
+use crate::{SnowID, SnowIDExtractor};
+
+/// Shared provider consulted for a new node ID in a forked child
+type Provider = Arc<dyn NodeIdProvider + Send + Sync>;
+
+/// A generator that detects running in a forked child process
+///
+/// The fast path (another ID within the current tick) takes no lock and makes no
+/// `getpid` call; the process ID is compared only when a new tick is claimed.
+pub struct ForkSafeSnowID {
+    generator: SnowID,
+    pid: u32,
+    provider: Option<Provider>,
+    current_pid: fn() -> u32,
+    // Replacement generator with a new node ID, set once in a forked child
+    child: OnceLock<Box<ForkSafeSnowID>>,
+    // Serializes reassignment so the provider is consulted once per fork
+    reassign: Mutex<()>,
+}
+
+impl ForkSafeSnowID {
+    /// Guard a generator; in a forked child every call fails with `ForkError::Detected`
+    pub fn new(generator: SnowID) -> Self {
+        Self::with_pid_source(generator, None, std::process::id)
+    }
+
+    /// Guard a generator; in a forked child it switches to a node ID from `provider`
+    ///
+    /// The provider must hand out a node ID no other process uses, e.g. via `NodeLock`
+    /// or a lease. Returning the parent's node ID fails with `ForkError::Detected`.
+    pub fn with_provider(
+        generator: SnowID,
+        provider: impl NodeIdProvider + Send + Sync + 'static,
+    ) -> Self {
+        Self::with_pid_source(generator, Some(Arc::new(provider)), std::process::id)
+    }
+
+    pub(crate) fn with_pid_source(
+        generator: SnowID,
+        provider: Option<Provider>,
+        current_pid: fn() -> u32,
+    ) -> Self {
+        Self {
+            generator,
+            pid: current_pid(),
+            provider,
+            current_pid,
+            child: OnceLock::new(),
+            reassign: Mutex::new(()),
+        }
+    }
+
+    /// Generate a new SnowID
+    ///
+    /// # Panics
+    /// Panics in a forked child without a provider, or once the timestamp no longer fits
+    /// the configured layout (see `try_generate`)
+    #[inline]
+    pub fn generate(&self) -> u64 {
+        match self.try_generate() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Generate a new SnowID, checking for a fork before claiming a new tick
+    #[inline]
+    pub fn try_generate(&self) -> Result<u64, ForkError> {
+        let generator = self.active();
+        let pid = generator.pid;
+        match generator
+            .generator
+            .try_generate_if(|| (generator.current_pid)() == pid)
+            .map_err(ForkError::Generate)?
+        {
+            Some(id) => Ok(id),
+            None => {
+                generator.check_fork()?;
+                self.try_generate()
+            }
+        }
+    }
+
+    /// Check for a fork now, e.g. from an after-fork hook, reassigning the node if configured
+    ///
+    /// Returns `Ok(true)` if the generator switched to a new node ID
+    pub fn check_fork(&self) -> Result<bool, ForkError> {
+        let generator = self.active();
+        let pid = (generator.current_pid)();
+        if pid == generator.pid {
+            return Ok(false);
+        }
+        generator.handle_fork(pid)
+    }
+
+    #[cold]
+    fn handle_fork(&self, pid: u32) -> Result<bool, ForkError> {
+        let _guard = self.reassign.lock().unwrap_or_else(|e| e.into_inner());
+        // Another thread of this child may have reassigned already
+        if self.child.get().is_some() {
+            return Ok(true);
+        }
+
+        let detected = ForkError::Detected {
+            original_pid: self.pid,
+            pid,
+        };
+        let Some(provider) = &self.provider else {
+            return Err(detected);
+        };
+        let config = self.generator.config.clone();
+        let node_id = provider.node_id(&config).map_err(ForkError::Reassign)?;
+        if node_id == self.generator.node_id {
+            return Err(detected);
+        }
+
+        let generator = SnowID::with_config(node_id, config).map_err(ForkError::Generate)?;
+        let child = Self::with_pid_source(generator, Some(provider.clone()), self.current_pid);
+        let _ = self.child.set(Box::new(child));
+        Ok(true)
+    }
+
+    /// Generator currently in use: the latest replacement after any forks
+    #[inline]
+    fn active(&self) -> &Self {
+        let mut generator = self;
+        while let Some(child) = generator.child.get() {
+            generator = child;
+        }
+        generator
+    }
+
+    /// Current node ID (changes after a fork when a provider is configured)
+    pub fn node_id(&self) -> u16 {
+        self.active().generator.node_id
+    }
+
+    /// Extractor for the generator's layout
+    pub fn extractor(&self) -> SnowIDExtractor {
+        self.active().generator.extract.clone()
+    }
+}
+
+impl fmt::Debug for ForkSafeSnowID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let active = self.active();
+        f.debug_struct("ForkSafeSnowID")
+            .field("generator", &active.generator)
+            .field("pid", &active.pid)
+            .field("has_provider", &active.provider.is_some())
+            .finish()
+    }
+}
+
+/// Errors from a fork-safe generator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkError {
+    /// Running in a forked child that may not reuse the parent's node ID
+    Detected { original_pid: u32, pid: u32 },
+    /// The provider could not supply a new node ID
+    Reassign(NodeIdError),
+    /// The underlying generator failed
+    Generate(SnowIDError),
+}
+
+impl fmt::Display for ForkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForkError::Detected { original_pid, pid } => write!(
+                f,
+                "Generator created in process {} used in forked process {}, \
+                 refusing to issue duplicate IDs",
+                original_pid, pid
+            ),
+            ForkError::Reassign(e) => write!(f, "Node reassignment after fork failed: {}", e),
+            ForkError::Generate(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ForkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ForkError::Reassign(e) => Some(e),
+            ForkError::Generate(e) => Some(e),
+            ForkError::Detected { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::node::{StatefulSetOrdinal, StaticNodeId};
+
+    // Each test owns a fake PID so tests can "fork" independently
+    macro_rules! fake_pid {
+        ($name:ident) => {
+            static $name: AtomicU32 = AtomicU32::new(100);
+        };
+    }
+
+    #[test]
+    fn test_same_process_generates() {
+        let generator = ForkSafeSnowID::new(SnowID::new(1).unwrap());
+        assert_eq!(generator.check_fork(), Ok(false));
+        let id = generator.try_generate().unwrap();
+        assert_eq!(generator.extractor().node(id), 1);
+    }
+
+    #[test]
+    fn test_fork_without_provider_refuses() {
+        fake_pid!(PID);
+        let generator = ForkSafeSnowID::with_pid_source(SnowID::new(1).unwrap(), None, || {
+            PID.load(Ordering::Relaxed)
+        });
+        assert!(generator.try_generate().is_ok());
+
+        PID.store(101, Ordering::Relaxed);
+        let detected = Err(ForkError::Detected {
+            original_pid: 100,
+            pid: 101,
+        });
+        assert_eq!(generator.check_fork(), detected.clone().map(|_: u64| false));
+        // The next tick is checked without an explicit hook
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert_eq!(generator.try_generate(), detected);
+    }
+
+    #[test]
+    fn test_fork_checked_on_sequence_rollover() {
+        fake_pid!(PID);
+        let config = crate::SnowIDConfig::builder()
+            .node_bits(16)
+            .unwrap()
+            .build();
+        let generator =
+            ForkSafeSnowID::with_pid_source(SnowID::with_config(1, config).unwrap(), None, || {
+                PID.load(Ordering::Relaxed)
+            });
+        assert!(generator.try_generate().is_ok());
+        PID.store(102, Ordering::Relaxed);
+        // 64 sequence values per tick: rolling over claims a new tick, which is checked
+        let results: Vec<_> = (0..=64).map(|_| generator.try_generate()).collect();
+        assert!(results.iter().any(|result| result.is_err()));
+    }
+
+    #[test]
+    fn test_fork_reassigns_again_in_grandchild() {
+        fake_pid!(PID);
+        let generator = ForkSafeSnowID::with_pid_source(
+            SnowID::new(1).unwrap(),
+            Some(Arc::new(StatefulSetOrdinal::with_hostname("worker-7"))),
+            || PID.load(Ordering::Relaxed),
+        );
+        PID.store(500, Ordering::Relaxed);
+        assert_eq!(generator.check_fork(), Ok(true));
+        assert_eq!(generator.node_id(), 7);
+
+        // The provider keeps returning 7, which the grandchild may not reuse
+        PID.store(501, Ordering::Relaxed);
+        assert_eq!(
+            generator.check_fork(),
+            Err(ForkError::Detected {
+                original_pid: 500,
+                pid: 501
+            })
+        );
+    }
+
+    #[test]
+    fn test_fork_with_provider_reassigns() {
+        fake_pid!(PID);
+        let generator = ForkSafeSnowID::with_pid_source(
+            SnowID::new(1).unwrap(),
+            Some(Arc::new(StatefulSetOrdinal::with_hostname("worker-7"))),
+            || PID.load(Ordering::Relaxed),
+        );
+        assert_eq!(generator.node_id(), 1);
+
+        PID.store(200, Ordering::Relaxed);
+        let id = generator.try_generate().unwrap();
+        assert_eq!(generator.node_id(), 7);
+        assert_eq!(generator.extractor().node(id), 7);
+        // Reassigned once, then stable in the child
+        assert_eq!(generator.check_fork(), Ok(false));
+    }
+
+    #[test]
+    fn test_provider_returning_parent_node_refuses() {
+        fake_pid!(PID);
+        let generator = ForkSafeSnowID::with_pid_source(
+            SnowID::new(3).unwrap(),
+            Some(Arc::new(StaticNodeId(3))),
+            || PID.load(Ordering::Relaxed),
+        );
+        PID.store(300, Ordering::Relaxed);
+        assert!(matches!(
+            generator.try_generate(),
+            Err(ForkError::Detected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_provider_failure_reported() {
+        fake_pid!(PID);
+        let generator = ForkSafeSnowID::with_pid_source(
+            SnowID::new(3).unwrap(),
+            Some(Arc::new(StaticNodeId(5000))),
+            || PID.load(Ordering::Relaxed),
+        );
+        PID.store(400, Ordering::Relaxed);
+        assert!(matches!(
+            generator.check_fork(),
+            Err(ForkError::Reassign(NodeIdError::OutOfRange { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ForkError::Detected {
+            original_pid: 10,
+            pid: 11,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Generator created in process 10 used in forked process 11, refusing to issue duplicate IDs"
+        );
+    }
+}
@@ -2,59 +2,148 @@
 //!
 //! Core generate() implementation with fast and slow paths
 
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use super::SnowID;
 use super::state::State;
 use super::wait::next_backoff;
+use crate::error::SnowIDError;
 
 impl SnowID {
     /// Generate a new SnowID
+    ///
+    /// # Panics
+    /// Panics once the timestamp no longer fits a short layout such as `js_safe`
+    /// (see `try_generate`). Standard 42-bit layouts last 139 years past their epoch
+    /// and skip the check.
     #[inline]
     pub fn generate(&self) -> u64 {
+        match self.try_generate() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Generate a new SnowID, failing once the timestamp no longer fits the configured layout
+    ///
+    /// Never wraps around: IDs stay below `SnowIDConfig::max_id()`, which matters for
+    /// short-lived layouts such as `SnowIDConfigBuilder::js_safe`.
+    #[inline]
+    pub fn try_generate(&self) -> Result<u64, SnowIDError> {
+        match self.try_generate_if(|| true)? {
+            Some(id) => Ok(id),
+            None => unreachable!("new ticks are always allowed"),
+        }
+    }
+
+    /// Generate, asking `allow_new_tick` before claiming a new tick (also after a sequence
+    /// rollover); `Ok(None)` when it refuses. IDs within the current tick skip the check.
+    #[inline]
+    pub(crate) fn try_generate_if(
+        &self,
+        allow_new_tick: impl Fn() -> bool,
+    ) -> Result<Option<u64>, SnowIDError> {
+        self.try_generate_on(&self.state, allow_new_tick)
+    }
+
+    /// `try_generate_if` against an external state word, e.g. one shared through a memory map
+    #[inline]
+    pub(crate) fn try_generate_on(
+        &self,
+        state: &AtomicU64,
+        allow_new_tick: impl Fn() -> bool,
+    ) -> Result<Option<u64>, SnowIDError> {
         let now = self.now_ms();
-        let current = State::from_raw(self.state.load(Ordering::Acquire));
+        let current = State::from_raw(state.load(Ordering::Acquire));
 
         // Fast path 1: time advanced
         if now > current.timestamp() {
-            if let Some(id) = self.try_claim_millisecond(current, now) {
-                return id;
+            self.check_overflow(now)?;
+            if !allow_new_tick() {
+                return Ok(None);
+            }
+            if let Some(id) = self.try_claim_millisecond(state, current, now) {
+                return Ok(Some(id));
+            }
+            return self.generate_slow_path(state, &allow_new_tick);
+        }
+
+        // Fast path 2: same millisecond, sequence available (its timestamp was checked on claim)
+        if let Some(id) = self.try_increment_sequence(state, current) {
+            return Ok(Some(id));
+        }
+
+        self.generate_slow_path(state, &allow_new_tick)
+    }
+
+    /// Generate without waiting: `Ok(None)` when the current tick's sequence is exhausted
+    #[inline]
+    pub(crate) fn try_generate_nowait(&self) -> Result<Option<u64>, SnowIDError> {
+        loop {
+            let now = self.now_ms();
+            let current = State::from_raw(self.state.load(Ordering::Acquire));
+
+            if now > current.timestamp() {
+                self.check_overflow(now)?;
+                if let Some(id) = self.try_claim_millisecond(&self.state, current, now) {
+                    return Ok(Some(id));
+                }
+                continue;
+            }
+            if current.sequence() >= self.max_seq {
+                return Ok(None);
+            }
+            if let Some(id) = self.try_increment_sequence(&self.state, current) {
+                return Ok(Some(id));
             }
-            return self.generate_slow_path();
         }
+    }
 
-        // Fast path 2: same millisecond, sequence available
-        if let Some(id) = self.try_increment_sequence(current) {
-            return id;
+    /// Fail if a newly claimed tick no longer fits a short layout
+    #[inline(always)]
+    fn check_overflow(&self, now: u64) -> Result<(), SnowIDError> {
+        if self.can_overflow && now > self.ts_mask {
+            return Err(self.timestamp_overflow(now));
         }
+        Ok(())
+    }
 
-        self.generate_slow_path()
+    #[cold]
+    fn timestamp_overflow(&self, timestamp: u64) -> SnowIDError {
+        SnowIDError::TimestampOverflow {
+            timestamp,
+            max: self.ts_mask,
+        }
     }
 
     /// Try to claim new millisecond with sequence 0
     #[inline]
-    pub(crate) fn try_claim_millisecond(&self, current: State, new_ts: u64) -> Option<u64> {
+    pub(crate) fn try_claim_millisecond(
+        &self,
+        state: &AtomicU64,
+        current: State,
+        new_ts: u64,
+    ) -> Option<u64> {
         let new_state = State::new(new_ts, 0);
-        self.cas_state(current, new_state)
-            .then(|| self.assemble_id(new_ts, 0))
+        Self::cas_state(state, current, new_state).then(|| self.assemble_id(new_ts, 0))
     }
 
     /// Try to increment sequence within current millisecond
     #[inline]
-    pub(crate) fn try_increment_sequence(&self, current: State) -> Option<u64> {
+    pub(crate) fn try_increment_sequence(&self, state: &AtomicU64, current: State) -> Option<u64> {
         if current.sequence() >= self.max_seq {
             return None;
         }
         let new_seq = current.sequence() + 1;
         let new_state = State::new(current.timestamp(), new_seq);
-        self.cas_state(current, new_state)
+        Self::cas_state(state, current, new_state)
             .then(|| self.assemble_id(current.timestamp(), new_seq))
     }
 
     /// Atomic compare-and-swap on state
     #[inline(always)]
-    pub(crate) fn cas_state(&self, expected: State, new: State) -> bool {
-        self.state
+    pub(crate) fn cas_state(state: &AtomicU64, expected: State, new: State) -> bool {
+        state
             .compare_exchange_weak(
                 expected.raw(),
                 new.raw(),
@@ -67,22 +156,30 @@ pub(crate) fn cas_state(&self, expected: State, new: State) -> bool {
     /// Slow path for contended generation
     #[cold]
     #[inline(never)]
-    fn generate_slow_path(&self) -> u64 {
+    fn generate_slow_path(
+        &self,
+        state: &AtomicU64,
+        allow_new_tick: &impl Fn() -> bool,
+    ) -> Result<Option<u64>, SnowIDError> {
         let mut backoff_ms = 1u64;
 
         loop {
             let now = self.now_ms();
-            let current = State::from_raw(self.state.load(Ordering::Acquire));
+            let current = State::from_raw(state.load(Ordering::Acquire));
 
             if now > current.timestamp() {
-                if let Some(id) = self.try_claim_millisecond(current, now) {
-                    return id;
+                self.check_overflow(now)?;
+                if !allow_new_tick() {
+                    return Ok(None);
+                }
+                if let Some(id) = self.try_claim_millisecond(state, current, now) {
+                    return Ok(Some(id));
                 }
                 continue;
             }
 
-            if let Some(id) = self.try_increment_sequence(current) {
-                return id;
+            if let Some(id) = self.try_increment_sequence(state, current) {
+                return Ok(Some(id));
             }
 
             self.wait_next_millis(current.timestamp(), backoff_ms);
@@ -5,9 +5,17 @@
 //! - `time` - Wall-clock time utilities
 //! - `wait` - Spin and backoff strategies
 //! - `generate` - ID generation logic
+//! - `prefix_methods` - Stripe-style prefixed IDs
+//! - `node_methods` - Node ID discovery and role-based creation
+//! - `registry` - Process-wide guard against duplicate generators
+//! - `snapshot_methods` - State export and import for handoff
 
 mod base62_methods;
 mod generate;
+mod node_methods;
+mod prefix_methods;
+mod registry;
+mod snapshot_methods;
 mod state;
 mod time;
 mod wait;
@@ -17,8 +25,10 @@
 use crate::config::SnowIDConfig;
 use crate::error::SnowIDError;
 use crate::extractor::SnowIDExtractor;
+use crate::node::NodeLock;
 
 use time::time_since_epoch;
+pub(crate) use time::unix_time_ms;
 use wait::{sleep_until_next_ms, spin_wait};
 
 /// Main ID generator with cache-line alignment
@@ -31,12 +41,19 @@ pub struct SnowID {
     pub(crate) max_seq: u16,
     ts_shift: u8,
     ts_mask: u64,
+    // Only short layouts (fewer timestamp bits than the standard 42) check for overflow
+    pub(crate) can_overflow: bool,
     epoch: u64,
+    tick_ms: u64,
 
     // === Cold path fields ===
     pub node_id: u16,
     pub config: SnowIDConfig,
     pub extract: SnowIDExtractor,
+    // Held for the generator's lifetime when created by `claim_local`
+    node_lock: Option<NodeLock>,
+    // Set for generators tracked by `register` or `shared`
+    registry_key: Option<registry::RegistryKey>,
 }
 
 impl SnowID {
@@ -59,6 +76,9 @@ fn validate_node_id(node_id: u16, config: &SnowIDConfig) -> Result<(), SnowIDErr
         if node_id > max {
             return Err(SnowIDError::InvalidNodeId { node_id, max });
         }
+        if !config.node_in_roles(node_id) {
+            return Err(SnowIDError::NodeOutsideRoles { node_id });
+        }
         Ok(())
     }
 
@@ -69,10 +89,14 @@ fn build(node_id: u16, config: SnowIDConfig) -> Self {
             max_seq: config.max_sequence_id(),
             ts_shift: config.timestamp_shift(),
             ts_mask: config.timestamp_mask(),
+            can_overflow: config.timestamp_bits() < Self::TIMESTAMP_BITS as u8,
             epoch: config.epoch(),
+            tick_ms: config.tick_ms(),
             node_id,
+            extract: SnowIDExtractor::new(config.clone()),
             config,
-            extract: SnowIDExtractor::new(config),
+            node_lock: None,
+            registry_key: None,
         }
     }
 
@@ -81,9 +105,15 @@ fn compute_node_prefix(node_id: u16, config: &SnowIDConfig) -> u64 {
         (node_id as u64) << config.node_shift()
     }
 
+    /// Current time since epoch in ticks (milliseconds unless a coarser tick is configured)
     #[inline(always)]
     pub(crate) fn now_ms(&self) -> u64 {
-        time_since_epoch(self.epoch)
+        let ms = time_since_epoch(self.epoch);
+        if self.tick_ms == 1 {
+            ms
+        } else {
+            ms / self.tick_ms
+        }
     }
 
     #[inline(always)]
@@ -0,0 +1,85 @@
+//! Node discovery convenience methods for SnowID generator
+
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::config::SnowIDConfig;
+use crate::error::SnowIDError;
+use crate::node::{NodeIdError, NodeIdProvider, NodeLock};
+
+use super::SnowID;
+
+impl SnowID {
+    /// Create with a node ID from a provider, e.g. `StatefulSetOrdinal` or `EnvVar`
+    pub fn from_provider(
+        provider: &impl NodeIdProvider,
+        config: SnowIDConfig,
+    ) -> Result<Self, NodeIdError> {
+        let node_id = provider.node_id(&config)?;
+        // Reject out-of-range IDs even from custom providers that skip validation
+        Self::with_config(node_id, config).map_err(|e| node_error(node_id, e))
+    }
+
+    /// Create with the `index`-th node ID of a role declared via `SnowIDConfigBuilder::node_role`
+    ///
+    /// `SnowID::for_role("batch", 3, config)` uses node 903 when `batch` is `900..=999`
+    pub fn for_role(role: &str, index: u16, config: SnowIDConfig) -> Result<Self, SnowIDError> {
+        let declared = config.role(role).ok_or_else(|| SnowIDError::UnknownRole {
+            role: role.to_owned(),
+        })?;
+        let node_id = declared
+            .node(index)
+            .ok_or_else(|| SnowIDError::RoleIndexOutOfRange {
+                role: declared.name.clone(),
+                index,
+                size: declared.size(),
+            })?;
+        Self::with_config(node_id, config)
+    }
+
+    /// Create with default configuration, claiming the first free node ID in `range`
+    ///
+    /// Takes an exclusive lock on `dir/<node>.lock` for as long as the generator lives,
+    /// so worker processes on one host get distinct node IDs
+    pub fn claim_local(
+        dir: impl AsRef<Path>,
+        range: RangeInclusive<u16>,
+    ) -> Result<Self, NodeIdError> {
+        Self::claim_local_with_config(dir, range, SnowIDConfig::default())
+    }
+
+    /// Create with custom configuration, claiming the first free node ID in `range`
+    ///
+    /// With node roles declared, only node IDs inside a role are tried
+    pub fn claim_local_with_config(
+        dir: impl AsRef<Path>,
+        range: RangeInclusive<u16>,
+        config: SnowIDConfig,
+    ) -> Result<Self, NodeIdError> {
+        let lock = NodeLock::claim(dir, range, &config)?;
+        let node_id = lock.node_id();
+        let mut generator =
+            Self::with_config(node_id, config).map_err(|e| node_error(node_id, e))?;
+        generator.node_lock = Some(lock);
+        Ok(generator)
+    }
+
+    /// The lock file held by a generator created with `claim_local`
+    #[inline]
+    pub fn node_lock(&self) -> Option<&NodeLock> {
+        self.node_lock.as_ref()
+    }
+}
+
+/// Report a node ID the generator rejects as a node discovery error
+fn node_error(node_id: u16, e: SnowIDError) -> NodeIdError {
+    match e {
+        SnowIDError::InvalidNodeId { max, .. } => NodeIdError::OutOfRange {
+            node_id: node_id as u64,
+            max,
+        },
+        _ => NodeIdError::Invalid {
+            value: e.to_string(),
+        },
+    }
+}
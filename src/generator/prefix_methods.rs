@@ -0,0 +1,12 @@
+//! Prefixed ID convenience methods for SnowID generator
+
+use crate::prefix::{Prefix, PrefixError, PrefixedSnowID};
+
+use super::SnowID;
+
+impl SnowID {
+    /// Create a handle generating Stripe-style `<prefix>_<base62>` IDs, e.g. `usr_2qPfVQh7Jw9`
+    pub fn prefixed(&self, prefix: &str) -> Result<PrefixedSnowID<'_>, PrefixError> {
+        Ok(PrefixedSnowID::new(self, Prefix::new(prefix)?))
+    }
+}
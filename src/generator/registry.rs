@@ -0,0 +1,123 @@
+//! Opt-in process-wide registry of live generators
+//!
+//! Two generators with the same node ID and layout both start from an empty state and
+//! produce identical IDs within the same tick. `SnowID::register` rejects such a duplicate,
+//! `SnowID::shared` hands back the live instance instead. Plain `SnowID::new` and
+//! `SnowID::with_config` are not tracked.
+//!
+//! A dropped tracked generator leaves its last state behind, so the next generator for the
+//! same node and layout resumes after it instead of repeating IDs within the same tick.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+use std::thread;
+
+use crate::config::SnowIDConfig;
+use crate::error::SnowIDError;
+
+use super::SnowID;
+
+/// Node ID plus every layout parameter that affects the generated bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct RegistryKey {
+    node_id: u16,
+    timestamp_bits: u8,
+    node_bits: u8,
+    sequence_bits: u8,
+    epoch: u64,
+    tick_ms: u64,
+}
+
+impl RegistryKey {
+    fn new(node_id: u16, config: &SnowIDConfig) -> Self {
+        Self {
+            node_id,
+            timestamp_bits: config.timestamp_bits(),
+            node_bits: config.node_bits(),
+            sequence_bits: config.sequence_bits(),
+            epoch: config.epoch(),
+            tick_ms: config.tick_ms(),
+        }
+    }
+}
+
+/// Tracked generator of one node and layout
+struct Entry {
+    live: Weak<SnowID>,
+    // Packed state left by the dropped generator; `None` while it is alive or being dropped
+    last_state: Option<u64>,
+}
+
+/// Tracked generators; entries outlive their generator to keep its last state
+static REGISTRY: LazyLock<Mutex<HashMap<RegistryKey, Entry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+impl SnowID {
+    /// Create a tracked generator, failing if one for the same node and layout is still alive
+    pub fn register(node_id: u16, config: SnowIDConfig) -> Result<Arc<Self>, SnowIDError> {
+        Self::track(node_id, config, |_| {
+            Err(SnowIDError::DuplicateGenerator { node_id })
+        })
+    }
+
+    /// Get the live tracked generator for the node and layout, creating it if there is none
+    pub fn shared(node_id: u16, config: SnowIDConfig) -> Result<Arc<Self>, SnowIDError> {
+        Self::track(node_id, config, Ok)
+    }
+
+    /// Create a tracked generator resuming after the last one, or pass the live one to `on_live`
+    fn track(
+        node_id: u16,
+        config: SnowIDConfig,
+        on_live: impl FnOnce(Arc<Self>) -> Result<Arc<Self>, SnowIDError>,
+    ) -> Result<Arc<Self>, SnowIDError> {
+        Self::validate_node_id(node_id, &config)?;
+        let key = RegistryKey::new(node_id, &config);
+        loop {
+            let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+            let resume = match registry.get(&key) {
+                None => 0,
+                Some(entry) => {
+                    if let Some(live) = entry.live.upgrade() {
+                        return on_live(live);
+                    }
+                    match entry.last_state {
+                        Some(state) => state,
+                        None => {
+                            // The last generator is being dropped; let it record its state
+                            drop(registry);
+                            thread::yield_now();
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let mut generator = Self::build(node_id, config);
+            generator.state = AtomicU64::new(resume);
+            generator.registry_key = Some(key);
+            let generator = Arc::new(generator);
+            registry.insert(
+                key,
+                Entry {
+                    live: Arc::downgrade(&generator),
+                    last_state: None,
+                },
+            );
+            return Ok(generator);
+        }
+    }
+}
+
+impl Drop for SnowID {
+    fn drop(&mut self) {
+        let Some(key) = self.registry_key else {
+            return;
+        };
+        let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = registry.get_mut(&key) {
+            entry.last_state = Some(self.state.load(Ordering::Acquire));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Snapshot and restore methods for SnowID generator
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config::SnowIDConfig;
+use crate::error::SnowIDError;
+use crate::snapshot::{SnapshotError, SnowIDSnapshot};
+
+use super::SnowID;
+use super::state::State;
+
+impl SnowID {
+    /// Capture the last issued timestamp and sequence with the node and layout
+    ///
+    /// Stop generating before taking the snapshot: IDs issued afterwards are not covered.
+    /// Use `into_snapshot` to have the compiler enforce that.
+    pub fn snapshot(&self) -> SnowIDSnapshot {
+        let state = State::from_raw(self.state.load(Ordering::Acquire));
+        SnowIDSnapshot {
+            node_id: self.node_id,
+            timestamp: state.timestamp(),
+            sequence: state.sequence(),
+            timestamp_bits: self.config.timestamp_bits(),
+            node_bits: self.config.node_bits(),
+            sequence_bits: self.config.sequence_bits(),
+            epoch: self.config.epoch(),
+            tick_ms: self.config.tick_ms(),
+        }
+    }
+
+    /// Consume the generator and capture its final state for handoff
+    ///
+    /// Unlike `snapshot`, the generator cannot issue IDs afterwards, so the successor
+    /// restored from the snapshot can never overlap it
+    pub fn into_snapshot(self) -> SnowIDSnapshot {
+        self.snapshot()
+    }
+
+    /// Create a generator that continues strictly after the snapshot
+    ///
+    /// Uses the snapshot's layout with default spin settings. Snapshots do not carry node
+    /// roles, so the restored configuration has none; use `restore_with_config` to keep them.
+    pub fn restore(snapshot: SnowIDSnapshot) -> Result<Self, SnapshotError> {
+        Self::restore_with_config(snapshot, snapshot.config()?)
+    }
+
+    /// Create a generator with custom configuration that continues strictly after the snapshot
+    ///
+    /// The configuration's layout must match the snapshot's, and when it declares node
+    /// roles the snapshot's node must fall inside one of them
+    pub fn restore_with_config(
+        snapshot: SnowIDSnapshot,
+        config: SnowIDConfig,
+    ) -> Result<Self, SnapshotError> {
+        if !snapshot.matches_layout(&config) {
+            return Err(SnapshotError::LayoutMismatch);
+        }
+        if let Err(SnowIDError::NodeOutsideRoles { node_id }) =
+            Self::validate_node_id(snapshot.node_id, &config)
+        {
+            return Err(SnapshotError::NodeOutsideRoles { node_id });
+        }
+        if snapshot.node_id > config.max_node_id()
+            || snapshot.timestamp > config.timestamp_mask()
+            || snapshot.sequence > config.max_sequence_id()
+        {
+            return Err(SnapshotError::InvalidState {
+                node_id: snapshot.node_id,
+                timestamp: snapshot.timestamp,
+                sequence: snapshot.sequence,
+            });
+        }
+
+        let mut generator = Self::build(snapshot.node_id, config);
+        // Same packed state the predecessor held after its last ID
+        generator.state = AtomicU64::new(State::new(snapshot.timestamp, snapshot.sequence).raw());
+        Ok(generator)
+    }
+}
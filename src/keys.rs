@@ -0,0 +1,211 @@
+//! Order-preserving binary keys for embedded key-value stores (sled, RocksDB, LMDB)
+//!
+//! Keys compare bytewise in the same order as the numeric IDs:
+//! - `encode` / `decode`: Big-endian bytes, oldest first
+//! - `encode_desc` / `decode_desc`: Inverted bytes, newest first
+//! - `encode_composite` / `decode_composite`: `(tenant_id, snowid)` keys grouped by tenant,
+//!   with `tenant_prefix` for prefix scans over one tenant
+//! - `encode_composite_desc` / `decode_composite_desc`: Tenants ascending, IDs newest first
+
+use std::error::Error;
+use std::fmt;
+
+/// Size of a single ID key in bytes
+pub const LEN: usize = 8;
+
+/// Size of a composite `(tenant_id, snowid)` key in bytes
+pub const COMPOSITE_LEN: usize = 2 * LEN;
+
+/// Ascending key: big-endian bytes
+#[inline]
+pub const fn encode(id: u64) -> [u8; LEN] {
+    id.to_be_bytes()
+}
+
+/// Decode an ascending key
+pub fn decode(key: &[u8]) -> Result<u64, KeyError> {
+    Ok(u64::from_be_bytes(fixed(key)?))
+}
+
+/// Descending key: newest IDs sort first
+#[inline]
+pub const fn encode_desc(id: u64) -> [u8; LEN] {
+    (!id).to_be_bytes()
+}
+
+/// Decode a descending key
+pub fn decode_desc(key: &[u8]) -> Result<u64, KeyError> {
+    Ok(!u64::from_be_bytes(fixed(key)?))
+}
+
+/// Composite key ordered by tenant, then ID ascending
+#[inline]
+pub const fn encode_composite(tenant_id: u64, id: u64) -> [u8; COMPOSITE_LEN] {
+    join(encode(tenant_id), encode(id))
+}
+
+/// Decode a composite key into `(tenant_id, snowid)`
+pub fn decode_composite(key: &[u8]) -> Result<(u64, u64), KeyError> {
+    let key: [u8; COMPOSITE_LEN] = fixed(key)?;
+    let (tenant, id) = key.split_at(LEN);
+    Ok((decode(tenant)?, decode(id)?))
+}
+
+/// Composite key ordered by tenant ascending, then ID newest first
+#[inline]
+pub const fn encode_composite_desc(tenant_id: u64, id: u64) -> [u8; COMPOSITE_LEN] {
+    join(encode(tenant_id), encode_desc(id))
+}
+
+/// Decode a composite descending key into `(tenant_id, snowid)`
+pub fn decode_composite_desc(key: &[u8]) -> Result<(u64, u64), KeyError> {
+    let key: [u8; COMPOSITE_LEN] = fixed(key)?;
+    let (tenant, id) = key.split_at(LEN);
+    Ok((decode(tenant)?, decode_desc(id)?))
+}
+
+/// Prefix shared by all composite keys of a tenant, for prefix scans
+#[inline]
+pub const fn tenant_prefix(tenant_id: u64) -> [u8; LEN] {
+    encode(tenant_id)
+}
+
+const fn join(high: [u8; LEN], low: [u8; LEN]) -> [u8; COMPOSITE_LEN] {
+    let mut key = [0u8; COMPOSITE_LEN];
+    let mut i = 0;
+    while i < LEN {
+        key[i] = high[i];
+        key[LEN + i] = low[i];
+        i += 1;
+    }
+    key
+}
+
+fn fixed<const N: usize>(key: &[u8]) -> Result<[u8; N], KeyError> {
+    key.try_into().map_err(|_| KeyError::InvalidLength {
+        len: key.len(),
+        expected: N,
+    })
+}
+
+/// Errors when decoding binary keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// Key has the wrong number of bytes
+    InvalidLength { len: usize, expected: usize },
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::InvalidLength { len, expected } => {
+                write!(f, "Invalid key length {}, expected {} bytes", len, expected)
+            }
+        }
+    }
+}
+
+impl Error for KeyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS: [u64; 9] = [
+        0,
+        1,
+        255,
+        256,
+        0xFFFF_FFFF,
+        1 << 32,
+        151819733950271234,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    #[test]
+    fn test_roundtrip() {
+        for id in IDS {
+            assert_eq!(decode(&encode(id)).unwrap(), id);
+            assert_eq!(decode_desc(&encode_desc(id)).unwrap(), id);
+            assert_eq!(decode_composite(&encode_composite(7, id)).unwrap(), (7, id));
+            assert_eq!(
+                decode_composite_desc(&encode_composite_desc(7, id)).unwrap(),
+                (7, id)
+            );
+        }
+    }
+
+    #[test]
+    fn test_byte_order_matches_numeric_order() {
+        for a in IDS {
+            for b in IDS {
+                assert_eq!(encode(a).cmp(&encode(b)), a.cmp(&b));
+                assert_eq!(encode_desc(a).cmp(&encode_desc(b)), b.cmp(&a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_composite_order() {
+        let tenants = [0u64, 1, 300, u64::MAX];
+        for ta in tenants {
+            for tb in tenants {
+                for a in IDS {
+                    for b in IDS {
+                        assert_eq!(
+                            encode_composite(ta, a).cmp(&encode_composite(tb, b)),
+                            (ta, a).cmp(&(tb, b))
+                        );
+                        assert_eq!(
+                            encode_composite_desc(ta, a).cmp(&encode_composite_desc(tb, b)),
+                            ta.cmp(&tb).then(b.cmp(&a))
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tenant_prefix() {
+        let key = encode_composite(42, 151819733950271234);
+        assert!(key.starts_with(&tenant_prefix(42)));
+        assert!(!key.starts_with(&tenant_prefix(43)));
+        assert!(encode_composite_desc(42, 1).starts_with(&tenant_prefix(42)));
+    }
+
+    #[test]
+    fn test_generated_ids_sort_by_key() {
+        let generator = crate::SnowID::new(1).unwrap();
+        let ids: Vec<u64> = (0..1000).map(|_| generator.generate()).collect();
+        let keys: Vec<_> = ids.iter().map(|&id| encode(id)).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert_eq!(
+            decode(&[0; 7]),
+            Err(KeyError::InvalidLength {
+                len: 7,
+                expected: LEN
+            })
+        );
+        assert_eq!(
+            decode_composite(&[0; 8]),
+            Err(KeyError::InvalidLength {
+                len: 8,
+                expected: COMPOSITE_LEN
+            })
+        );
+        assert_eq!(
+            KeyError::InvalidLength {
+                len: 7,
+                expected: 8
+            }
+            .to_string(),
+            "Invalid key length 7, expected 8 bytes"
+        );
+    }
+}
@@ -0,0 +1,463 @@
+//! Decoding and migrating IDs across layouts
+//!
+//! While moving to a new epoch or bit layout, old and new IDs coexist. A `LayoutSet` lists the
+//! layouts with how to recognise each one: a version bit above the layout's highest ID bit, an
+//! ID range, or a fallback. `decode` picks the first matching layout. Version bits need a layout
+//! narrower than 64 bits (JavaScript-safe layouts leave bits 53-63 free); the standard 64-bit
+//! layouts use every bit and are told apart by range instead.
+//!
+//! `LayoutMigration` re-encodes old IDs into the new layout, keeping the Unix time, node and
+//! sequence. Re-encoding preserves order, so sorted old IDs stay sorted after migration.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::config::SnowIDConfig;
+use crate::extractor::SnowIDExtractor;
+
+/// How a `LayoutSet` recognises the IDs of one layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutMatch {
+    /// IDs with this bit set; the bit is cleared before decoding
+    VersionBit(u8),
+    /// IDs within this inclusive range
+    Range(RangeInclusive<u64>),
+    /// Any ID no earlier layout matched
+    Fallback,
+}
+
+impl LayoutMatch {
+    #[inline]
+    fn matches(&self, id: u64) -> bool {
+        match self {
+            LayoutMatch::VersionBit(bit) => id & (1u64 << bit) != 0,
+            LayoutMatch::Range(range) => range.contains(&id),
+            LayoutMatch::Fallback => true,
+        }
+    }
+
+    #[inline]
+    fn payload(&self, id: u64) -> u64 {
+        match self {
+            LayoutMatch::VersionBit(bit) => id & !(1u64 << bit),
+            _ => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Layout {
+    name: &'static str,
+    matcher: LayoutMatch,
+    extract: SnowIDExtractor,
+}
+
+/// An ID decoded with the layout it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedId {
+    /// Name of the matching layout
+    pub layout: &'static str,
+    /// The ID with any version bit cleared
+    pub payload: u64,
+    /// Timestamp component in ticks since the layout's epoch
+    pub timestamp: u64,
+    /// Timestamp as Unix milliseconds
+    pub unix_ms: u64,
+    /// Node component
+    pub node: u16,
+    /// Sequence component
+    pub sequence: u16,
+}
+
+/// Layouts that coexist during a migration, checked in declaration order
+#[derive(Debug, Clone, Default)]
+pub struct LayoutSet {
+    layouts: Vec<Layout>,
+}
+
+impl LayoutSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layout whose IDs carry `bit`, which must lie above the layout's highest ID bit
+    pub fn with_version_bit(
+        self,
+        name: &'static str,
+        bit: u8,
+        config: SnowIDConfig,
+    ) -> Result<Self, LayoutError> {
+        check_version_bit(bit, &config)?;
+        self.push(name, LayoutMatch::VersionBit(bit), config)
+    }
+
+    /// Add a layout recognised by an inclusive ID range
+    pub fn with_range(
+        self,
+        name: &'static str,
+        range: RangeInclusive<u64>,
+        config: SnowIDConfig,
+    ) -> Result<Self, LayoutError> {
+        if range.is_empty() {
+            return Err(LayoutError::EmptyRange { name });
+        }
+        self.push(name, LayoutMatch::Range(range), config)
+    }
+
+    /// Add a layout for every ID the earlier layouts did not match
+    pub fn with_fallback(
+        self,
+        name: &'static str,
+        config: SnowIDConfig,
+    ) -> Result<Self, LayoutError> {
+        self.push(name, LayoutMatch::Fallback, config)
+    }
+
+    fn push(
+        mut self,
+        name: &'static str,
+        matcher: LayoutMatch,
+        config: SnowIDConfig,
+    ) -> Result<Self, LayoutError> {
+        for layout in &self.layouts {
+            if layout.name == name {
+                return Err(LayoutError::DuplicateLayout { name });
+            }
+            match overlap(&layout.matcher, &matcher) {
+                Overlap::None => {}
+                Overlap::Partial => {
+                    return Err(LayoutError::OverlappingRange {
+                        name,
+                        other: layout.name,
+                    });
+                }
+                Overlap::Full => {
+                    return Err(LayoutError::Unreachable {
+                        name,
+                        shadowed_by: layout.name,
+                    });
+                }
+            }
+        }
+
+        self.layouts.push(Layout {
+            name,
+            matcher,
+            extract: config.extractor(),
+        });
+        Ok(self)
+    }
+
+    fn find(&self, id: u64) -> Option<&Layout> {
+        self.layouts
+            .iter()
+            .find(|layout| layout.matcher.matches(id))
+    }
+
+    /// Name of the layout an ID belongs to
+    pub fn layout_of(&self, id: u64) -> Option<&'static str> {
+        self.find(id).map(|layout| layout.name)
+    }
+
+    /// Extractor of the layout an ID belongs to; clear any version bit before using it
+    pub fn extractor_for(&self, id: u64) -> Option<&SnowIDExtractor> {
+        self.find(id).map(|layout| &layout.extract)
+    }
+
+    /// Decode an ID with the layout it belongs to
+    pub fn decode(&self, id: u64) -> Result<DecodedId, LayoutError> {
+        let layout = self.find(id).ok_or(LayoutError::NoMatchingLayout { id })?;
+        let payload = layout.matcher.payload(id);
+        if payload > layout.extract.config().max_id() {
+            return Err(LayoutError::NotInLayout {
+                id,
+                layout: layout.name,
+            });
+        }
+
+        let (timestamp, node, sequence) = layout.extract.decompose(payload);
+        Ok(DecodedId {
+            layout: layout.name,
+            payload,
+            timestamp,
+            unix_ms: layout.extract.timestamp_ms(payload),
+            node,
+            sequence,
+        })
+    }
+
+    /// Names of the layouts, in match order
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.layouts.iter().map(|layout| layout.name)
+    }
+}
+
+/// How much of a new layout's IDs an earlier layout already claims
+enum Overlap {
+    None,
+    Partial,
+    Full,
+}
+
+/// Compare a new matcher against an earlier one
+fn overlap(earlier: &LayoutMatch, new: &LayoutMatch) -> Overlap {
+    let (any, all) = match (earlier, new) {
+        (LayoutMatch::Fallback, _) => (true, true),
+        (_, LayoutMatch::Fallback) => (false, false),
+        // Version-bit layouts are at most 53 bits wide with the bit above, so distinct bits never collide
+        (LayoutMatch::VersionBit(a), LayoutMatch::VersionBit(b)) => (a == b, a == b),
+        (LayoutMatch::Range(a), LayoutMatch::Range(b)) => (
+            a.start() <= b.end() && b.start() <= a.end(),
+            a.start() <= b.start() && b.end() <= a.end(),
+        ),
+        (LayoutMatch::VersionBit(bit), LayoutMatch::Range(range)) => {
+            (range_has_bit(range, *bit), range_all_bit(range, *bit))
+        }
+        (LayoutMatch::Range(range), LayoutMatch::VersionBit(bit)) => {
+            // IDs with the bit set run from `1 << bit` to `u64::MAX`
+            let tagged = (1u64 << bit)..=u64::MAX;
+            (
+                range_has_bit(range, *bit),
+                range.start() <= tagged.start() && tagged.end() <= range.end(),
+            )
+        }
+    };
+    match (any, all) {
+        (_, true) => Overlap::Full,
+        (true, false) => Overlap::Partial,
+        (false, false) => Overlap::None,
+    }
+}
+
+/// Whether some ID in `range` has `bit` set
+fn range_has_bit(range: &RangeInclusive<u64>, bit: u8) -> bool {
+    let mask = 1u64 << bit;
+    let start = *range.start();
+    if start & mask != 0 {
+        return true;
+    }
+    // Smallest ID above `start` with the bit set: keep the higher bits, set the bit, clear the rest
+    let below = mask.wrapping_shl(1).wrapping_sub(1);
+    (start & !below) | mask <= *range.end()
+}
+
+/// Whether every ID in `range` has `bit` set
+fn range_all_bit(range: &RangeInclusive<u64>, bit: u8) -> bool {
+    let mask = 1u64 << bit;
+    let (start, end) = (*range.start(), *range.end());
+    // Both ends tagged and no carry past the bit in between
+    start & mask != 0
+        && end & mask != 0
+        && start.checked_shr(u32::from(bit) + 1) == end.checked_shr(u32::from(bit) + 1)
+}
+
+fn check_version_bit(bit: u8, config: &SnowIDConfig) -> Result<(), LayoutError> {
+    let min = (u64::BITS - config.max_id().leading_zeros()) as u8;
+    if !(min..64).contains(&bit) {
+        return Err(LayoutError::InvalidVersionBit { bit, min });
+    }
+    Ok(())
+}
+
+/// Re-encodes IDs from an old layout into a new one, preserving order
+///
+/// Keep node IDs unchanged across the migration so re-encoded IDs cannot collide with
+/// IDs the new layout issues later
+#[derive(Debug, Clone)]
+pub struct LayoutMigration {
+    from: SnowIDExtractor,
+    to: SnowIDConfig,
+    version_bit: Option<u8>,
+}
+
+impl LayoutMigration {
+    /// Migrate from `from` to `to`
+    ///
+    /// The new tick must divide both the old tick and the epoch difference, so every old
+    /// timestamp maps to an exact, distinct new timestamp
+    pub fn new(from: SnowIDConfig, to: SnowIDConfig) -> Result<Self, LayoutError> {
+        let epoch_gap = from.epoch().abs_diff(to.epoch());
+        if !from.tick_ms().is_multiple_of(to.tick_ms()) || !epoch_gap.is_multiple_of(to.tick_ms()) {
+            return Err(LayoutError::IncompatibleTicks {
+                from_tick_ms: from.tick_ms(),
+                to_tick_ms: to.tick_ms(),
+                epoch_gap,
+            });
+        }
+        Ok(Self {
+            from: from.extractor(),
+            to,
+            version_bit: None,
+        })
+    }
+
+    /// Set `bit` on every re-encoded ID, matching `LayoutSet::with_version_bit`
+    pub fn with_version_bit(mut self, bit: u8) -> Result<Self, LayoutError> {
+        check_version_bit(bit, &self.to)?;
+        self.version_bit = Some(bit);
+        Ok(self)
+    }
+
+    /// Re-encode one old ID into the new layout
+    pub fn reencode(&self, id: u64) -> Result<u64, LayoutError> {
+        let from = self.from.config();
+        if id > from.max_id() {
+            return Err(LayoutError::NotInSourceLayout {
+                id,
+                max_id: from.max_id(),
+            });
+        }
+        let (_, node, sequence) = self.from.decompose(id);
+        let unix_ms = self.from.timestamp_ms(id);
+
+        let to = &self.to;
+        let since_epoch = unix_ms
+            .checked_sub(to.epoch())
+            .ok_or(LayoutError::BeforeEpoch {
+                id,
+                unix_ms,
+                epoch: to.epoch(),
+            })?;
+        let timestamp = since_epoch / to.tick_ms();
+        if timestamp > to.max_timestamp() {
+            return Err(LayoutError::DoesNotFit {
+                id,
+                field: "timestamp",
+                value: timestamp,
+                max: to.max_timestamp(),
+            });
+        }
+        if node > to.max_node_id() {
+            return Err(LayoutError::DoesNotFit {
+                id,
+                field: "node",
+                value: node as u64,
+                max: to.max_node_id() as u64,
+            });
+        }
+        if sequence > to.max_sequence_id() {
+            return Err(LayoutError::DoesNotFit {
+                id,
+                field: "sequence",
+                value: sequence as u64,
+                max: to.max_sequence_id() as u64,
+            });
+        }
+
+        let reencoded = (timestamp << to.timestamp_shift())
+            | ((node as u64) << to.node_shift())
+            | sequence as u64;
+        Ok(match self.version_bit {
+            Some(bit) => reencoded | (1u64 << bit),
+            None => reencoded,
+        })
+    }
+
+    /// Re-encode many IDs, stopping at the first that does not fit
+    pub fn reencode_all(&self, ids: &[u64]) -> Result<Vec<u64>, LayoutError> {
+        ids.iter().map(|&id| self.reencode(id)).collect()
+    }
+}
+
+/// Errors from `LayoutSet` and `LayoutMigration`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// Two layouts share a name
+    DuplicateLayout { name: &'static str },
+    /// A layout can never match because an earlier one claims the same IDs
+    Unreachable {
+        name: &'static str,
+        shadowed_by: &'static str,
+    },
+    /// A layout matches some, but not all, of the IDs an earlier layout claims
+    OverlappingRange {
+        name: &'static str,
+        other: &'static str,
+    },
+    /// A range layout covers no IDs
+    EmptyRange { name: &'static str },
+    /// The version bit overlaps the layout's ID bits or exceeds 63
+    InvalidVersionBit { bit: u8, min: u8 },
+    /// No layout matches the ID
+    NoMatchingLayout { id: u64 },
+    /// The ID has bits set above the matched layout's maximum
+    NotInLayout { id: u64, layout: &'static str },
+    /// The ID to migrate exceeds the old layout's maximum
+    NotInSourceLayout { id: u64, max_id: u64 },
+    /// Old timestamps cannot map exactly onto the new tick
+    IncompatibleTicks {
+        from_tick_ms: u64,
+        to_tick_ms: u64,
+        epoch_gap: u64,
+    },
+    /// The ID's time lies before the new epoch
+    BeforeEpoch { id: u64, unix_ms: u64, epoch: u64 },
+    /// A component of the ID exceeds the new layout
+    DoesNotFit {
+        id: u64,
+        field: &'static str,
+        value: u64,
+        max: u64,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::DuplicateLayout { name } => {
+                write!(f, "Layout '{}' is declared more than once", name)
+            }
+            LayoutError::Unreachable { name, shadowed_by } => write!(
+                f,
+                "Layout '{}' can never match, layout '{}' claims the same IDs",
+                name, shadowed_by
+            ),
+            LayoutError::OverlappingRange { name, other } => write!(
+                f,
+                "Layout '{}' overlaps layout '{}', shared IDs would only match '{}'",
+                name, other, other
+            ),
+            LayoutError::EmptyRange { name } => write!(f, "Layout '{}' has an empty range", name),
+            LayoutError::InvalidVersionBit { bit, min } => write!(
+                f,
+                "Version bit {} is invalid, must be between {} and 63",
+                bit, min
+            ),
+            LayoutError::NoMatchingLayout { id } => write!(f, "No layout matches ID {}", id),
+            LayoutError::NotInLayout { id, layout } => {
+                write!(f, "ID {} exceeds the maximum of layout '{}'", id, layout)
+            }
+            LayoutError::NotInSourceLayout { id, max_id } => {
+                write!(f, "ID {} exceeds the old layout maximum of {}", id, max_id)
+            }
+            LayoutError::IncompatibleTicks {
+                from_tick_ms,
+                to_tick_ms,
+                epoch_gap,
+            } => write!(
+                f,
+                "Tick of {} ms must divide the old tick of {} ms and the epoch gap of {} ms",
+                to_tick_ms, from_tick_ms, epoch_gap
+            ),
+            LayoutError::BeforeEpoch { id, unix_ms, epoch } => write!(
+                f,
+                "ID {} at unix ms {} lies before the new epoch {}",
+                id, unix_ms, epoch
+            ),
+            LayoutError::DoesNotFit {
+                id,
+                field,
+                value,
+                max,
+            } => write!(
+                f,
+                "ID {}: {} {} exceeds the new layout maximum of {}",
+                id, field, value, max
+            ),
+        }
+    }
+}
+
+impl Error for LayoutError {}
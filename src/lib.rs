@@ -9,22 +9,51 @@
 //! - 🔒 Thread-safe
 //! - 🌐 Distributed-ready
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+// Only the memory-mapped state of `SharedSnowID` opts back in
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 
 pub mod base62;
 mod config;
+pub mod crockford;
+pub mod encoding;
 mod error;
+mod explain;
 mod extractor;
+mod fork;
 mod generator;
+pub mod keys;
+mod layouts;
+pub mod node;
+pub mod obfuscate;
+mod pool;
+pub mod prefix;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "mmap")]
+mod shared;
+mod snapshot;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+mod validation;
 
 #[cfg(test)]
 pub mod tests;
 
 // Re-export main types
-pub use config::SnowIDConfig;
+pub use config::{NodeRole, SnowIDConfig, SnowIDConfigBuilder, SnowIDConfigError};
+pub use encoding::Encoding;
 pub use error::SnowIDError;
+pub use explain::SnowIDExplanation;
 pub use extractor::SnowIDExtractor;
+pub use fork::{ForkError, ForkSafeSnowID};
 pub use generator::SnowID;
+pub use layouts::{DecodedId, LayoutError, LayoutMatch, LayoutMigration, LayoutSet};
+pub use pool::{PoolError, PoolStrategy, SnowIDPool};
+#[cfg(feature = "mmap")]
+pub use shared::{SharedError, SharedSnowID};
+pub use snapshot::{SnapshotError, SnowIDSnapshot};
+pub use validation::{NodeFilter, ValidationError, ValidationPolicy};
 
 // Re-export base62 types at crate root for backward compatibility
 pub use base62::DecodeError as Base62DecodeError;
@@ -0,0 +1,490 @@
+//! TTL leases on node IDs with renewal and fencing epochs
+//!
+//! - `NodeLeaseBackend`: Storage that grants, renews and releases leases
+//! - `MemoryLeaseBackend`: In-process backend for tests and single-process setups
+//! - `FileLeaseBackend`: Lease table in a locked file, shared by processes on one host or NFS mount
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::generator::unix_time_ms;
+
+/// A granted lease on a node ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeLease {
+    /// Leased node ID
+    pub node_id: u16,
+    /// Fencing epoch, incremented every time the node is granted to a new holder
+    pub epoch: u64,
+    /// Holder that acquired the lease
+    pub owner: String,
+    /// Unix timestamp in milliseconds at which the lease lapses unless renewed
+    pub expires_at_ms: u64,
+}
+
+impl NodeLease {
+    /// Whether the lease has lapsed at the given Unix timestamp in milliseconds
+    #[inline]
+    pub fn is_expired_at(&self, now_unix_ms: u64) -> bool {
+        now_unix_ms >= self.expires_at_ms
+    }
+}
+
+/// Storage granting time-limited, fenced leases on node IDs
+///
+/// Implementations must guarantee that at most one unexpired lease exists per node ID and
+/// that the epoch of a node strictly increases with every new grant
+pub trait NodeLeaseBackend {
+    /// Grant a lease on the first node ID in `range` that is free or whose lease has lapsed
+    fn acquire(
+        &self,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+    ) -> Result<NodeLease, LeaseError>;
+
+    /// Extend an unexpired lease, failing with `Lost` if it lapsed or was taken over
+    fn renew(&self, lease: &NodeLease, ttl: Duration) -> Result<NodeLease, LeaseError>;
+
+    /// Give up a lease early so the node ID can be granted again
+    fn release(&self, lease: &NodeLease) -> Result<(), LeaseError>;
+}
+
+impl<B: NodeLeaseBackend + ?Sized> NodeLeaseBackend for Arc<B> {
+    fn acquire(
+        &self,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+    ) -> Result<NodeLease, LeaseError> {
+        (**self).acquire(owner, range, ttl)
+    }
+
+    fn renew(&self, lease: &NodeLease, ttl: Duration) -> Result<NodeLease, LeaseError> {
+        (**self).renew(lease, ttl)
+    }
+
+    fn release(&self, lease: &NodeLease) -> Result<(), LeaseError> {
+        (**self).release(lease)
+    }
+}
+
+/// Lease table keyed by node ID; released and lapsed entries keep their epoch
+type LeaseTable = BTreeMap<u16, NodeLease>;
+
+fn acquire_in(
+    table: &mut LeaseTable,
+    owner: &str,
+    range: RangeInclusive<u16>,
+    ttl: Duration,
+    now_ms: u64,
+) -> Result<NodeLease, LeaseError> {
+    let node_id = range
+        .clone()
+        .find(|node| {
+            table
+                .get(node)
+                .is_none_or(|lease| lease.is_expired_at(now_ms))
+        })
+        .ok_or(LeaseError::Exhausted {
+            start: *range.start(),
+            end: *range.end(),
+        })?;
+
+    let lease = NodeLease {
+        node_id,
+        epoch: table.get(&node_id).map_or(1, |prev| prev.epoch + 1),
+        owner: owner.to_owned(),
+        expires_at_ms: now_ms + ttl.as_millis() as u64,
+    };
+    table.insert(node_id, lease.clone());
+    Ok(lease)
+}
+
+fn renew_in(
+    table: &mut LeaseTable,
+    lease: &NodeLease,
+    ttl: Duration,
+    now_ms: u64,
+) -> Result<NodeLease, LeaseError> {
+    let current = table
+        .get_mut(&lease.node_id)
+        .filter(|current| current.epoch == lease.epoch && !current.is_expired_at(now_ms))
+        .ok_or(LeaseError::Lost {
+            node_id: lease.node_id,
+            epoch: lease.epoch,
+        })?;
+    current.expires_at_ms = now_ms + ttl.as_millis() as u64;
+    Ok(current.clone())
+}
+
+fn release_in(table: &mut LeaseTable, lease: &NodeLease) {
+    if let Some(current) = table.get_mut(&lease.node_id)
+        && current.epoch == lease.epoch
+    {
+        current.expires_at_ms = 0;
+    }
+}
+
+/// In-process lease backend
+#[derive(Debug, Default)]
+pub struct MemoryLeaseBackend {
+    table: Mutex<LeaseTable>,
+}
+
+impl MemoryLeaseBackend {
+    /// Create an empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_table<T>(&self, f: impl FnOnce(&mut LeaseTable) -> T) -> T {
+        let mut table = self.table.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut table)
+    }
+}
+
+impl NodeLeaseBackend for MemoryLeaseBackend {
+    fn acquire(
+        &self,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+    ) -> Result<NodeLease, LeaseError> {
+        self.with_table(|table| acquire_in(table, owner, range, ttl, unix_time_ms()))
+    }
+
+    fn renew(&self, lease: &NodeLease, ttl: Duration) -> Result<NodeLease, LeaseError> {
+        self.with_table(|table| renew_in(table, lease, ttl, unix_time_ms()))
+    }
+
+    fn release(&self, lease: &NodeLease) -> Result<(), LeaseError> {
+        self.with_table(|table| release_in(table, lease));
+        Ok(())
+    }
+}
+
+/// Lease backend storing its table in a file, guarded by an exclusive lock on `<path>.lock`
+///
+/// One line per node: `<node_id> <epoch> <expires_at_ms> <owner>`. Every update writes
+/// `<path>.tmp`, syncs it and renames it over the table, so a crash never loses epochs.
+#[derive(Debug, Clone)]
+pub struct FileLeaseBackend {
+    path: PathBuf,
+}
+
+impl FileLeaseBackend {
+    /// Use (and create if needed) the lease file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the lease file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn with_table<T>(
+        &self,
+        f: impl FnOnce(&mut LeaseTable) -> Result<T, LeaseError>,
+    ) -> Result<T, LeaseError> {
+        // The table itself is replaced on every update, so the lock lives in a separate file
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(sibling(&self.path, "lock"))
+            .map_err(|e| self.backend_error(e))?;
+        // Released when `lock` is dropped
+        lock.lock().map_err(|e| self.backend_error(e))?;
+
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(self.backend_error(e)),
+        };
+        let mut table = parse_table(&contents).ok_or_else(|| LeaseError::Backend {
+            reason: format!("{} is corrupted", self.path.display()),
+        })?;
+
+        let result = f(&mut table)?;
+        self.write_table(&table)
+            .map_err(|e| self.backend_error(e))?;
+        Ok(result)
+    }
+
+    /// Replace the table atomically: write a temp file, sync it, rename it over the table
+    fn write_table(&self, table: &LeaseTable) -> std::io::Result<()> {
+        let mut contents = String::new();
+        for lease in table.values() {
+            contents.push_str(&format!(
+                "{} {} {} {}\n",
+                lease.node_id, lease.epoch, lease.expires_at_ms, lease.owner
+            ));
+        }
+        let tmp = sibling(&self.path, "tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        sync_parent(&self.path)
+    }
+
+    fn backend_error(&self, e: std::io::Error) -> LeaseError {
+        LeaseError::Backend {
+            reason: format!("{}: {}", self.path.display(), e),
+        }
+    }
+}
+
+/// `<path>.<extension>` next to the lease table
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Persist the rename by syncing the containing directory
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => File::open(dir)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+/// Directories cannot be opened for syncing here; the rename itself is atomic
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn parse_table(contents: &str) -> Option<LeaseTable> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.splitn(4, ' ');
+            let lease = NodeLease {
+                node_id: fields.next()?.parse().ok()?,
+                epoch: fields.next()?.parse().ok()?,
+                expires_at_ms: fields.next()?.parse().ok()?,
+                owner: fields.next().unwrap_or_default().to_owned(),
+            };
+            Some((lease.node_id, lease))
+        })
+        .collect()
+}
+
+fn check_owner(owner: &str) -> Result<(), LeaseError> {
+    if owner.contains(['\n', '\r']) {
+        return Err(LeaseError::Backend {
+            reason: format!("Owner {:?} must not contain line breaks", owner),
+        });
+    }
+    Ok(())
+}
+
+impl NodeLeaseBackend for FileLeaseBackend {
+    fn acquire(
+        &self,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+    ) -> Result<NodeLease, LeaseError> {
+        check_owner(owner)?;
+        self.with_table(|table| acquire_in(table, owner, range, ttl, unix_time_ms()))
+    }
+
+    fn renew(&self, lease: &NodeLease, ttl: Duration) -> Result<NodeLease, LeaseError> {
+        self.with_table(|table| renew_in(table, lease, ttl, unix_time_ms()))
+    }
+
+    fn release(&self, lease: &NodeLease) -> Result<(), LeaseError> {
+        self.with_table(|table| {
+            release_in(table, lease);
+            Ok(())
+        })
+    }
+}
+
+/// Errors from lease backends and leased generators
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaseError {
+    /// Every node ID in the range holds an unexpired lease
+    Exhausted { start: u16, end: u16 },
+    /// The range reaches beyond the layout's maximum node ID
+    OutOfRange { node_id: u16, max: u16 },
+    /// The lease lapsed or was granted to another holder; stop using the node ID
+    Lost { node_id: u16, epoch: u64 },
+    /// The lease expired before it was renewed, so no more IDs are issued
+    Expired { node_id: u16, expires_at_ms: u64 },
+    /// The generator cannot issue IDs for a reason unrelated to the lease
+    Generate(crate::SnowIDError),
+    /// The backend storage failed
+    Backend { reason: String },
+}
+
+impl fmt::Display for LeaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaseError::Exhausted { start, end } => {
+                write!(f, "All node IDs in {}..={} are leased", start, end)
+            }
+            LeaseError::OutOfRange { node_id, max } => write!(
+                f,
+                "Node ID {} is invalid. Maximum allowed value is {}",
+                node_id, max
+            ),
+            LeaseError::Lost { node_id, epoch } => {
+                write!(f, "Lease on node {} (epoch {}) was lost", node_id, epoch)
+            }
+            LeaseError::Expired {
+                node_id,
+                expires_at_ms,
+            } => write!(
+                f,
+                "Lease on node {} expired at {}, no more IDs can be generated",
+                node_id, expires_at_ms
+            ),
+            LeaseError::Generate(e) => write!(f, "{}", e),
+            LeaseError::Backend { reason } => write!(f, "Lease backend error: {}", reason),
+        }
+    }
+}
+
+impl Error for LeaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LeaseError::Generate(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_utils::temp_dir;
+
+    const TTL: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_table_grants_free_nodes_first() {
+        let mut table = LeaseTable::new();
+        let a = acquire_in(&mut table, "a", 0..=1, TTL, 1000).unwrap();
+        let b = acquire_in(&mut table, "b", 0..=1, TTL, 1000).unwrap();
+        assert_eq!((a.node_id, a.epoch, a.expires_at_ms), (0, 1, 11_000));
+        assert_eq!((b.node_id, b.epoch), (1, 1));
+        assert_eq!(
+            acquire_in(&mut table, "c", 0..=1, TTL, 1000),
+            Err(LeaseError::Exhausted { start: 0, end: 1 })
+        );
+    }
+
+    #[test]
+    fn test_table_expiry_bumps_epoch_and_fences_old_holder() {
+        let mut table = LeaseTable::new();
+        let old = acquire_in(&mut table, "old", 0..=0, TTL, 1000).unwrap();
+
+        // Lapsed lease is granted again with a higher epoch
+        let new = acquire_in(&mut table, "new", 0..=0, TTL, 11_000).unwrap();
+        assert_eq!((new.node_id, new.epoch), (0, 2));
+
+        // The previous holder can neither renew nor release the new lease
+        assert_eq!(
+            renew_in(&mut table, &old, TTL, 11_001),
+            Err(LeaseError::Lost {
+                node_id: 0,
+                epoch: 1
+            })
+        );
+        release_in(&mut table, &old);
+        assert_eq!(table[&0], new);
+    }
+
+    #[test]
+    fn test_table_renew_and_release() {
+        let mut table = LeaseTable::new();
+        let lease = acquire_in(&mut table, "a", 3..=3, TTL, 1000).unwrap();
+        let renewed = renew_in(&mut table, &lease, TTL, 5000).unwrap();
+        assert_eq!(renewed.expires_at_ms, 15_000);
+        assert_eq!(renewed.epoch, lease.epoch);
+
+        // Renewing after expiry fails even if nobody took over
+        assert!(renew_in(&mut table, &renewed, TTL, 15_000).is_err());
+
+        let lease = acquire_in(&mut table, "b", 3..=3, TTL, 20_000).unwrap();
+        release_in(&mut table, &lease);
+        let next = acquire_in(&mut table, "c", 3..=3, TTL, 20_001).unwrap();
+        assert_eq!(next.epoch, lease.epoch + 1);
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = MemoryLeaseBackend::new();
+        let lease = backend.acquire("a", 0..=0, TTL).unwrap();
+        assert!(backend.acquire("b", 0..=0, TTL).is_err());
+        assert!(backend.renew(&lease, TTL).is_ok());
+        backend.release(&lease).unwrap();
+        assert_eq!(backend.acquire("b", 0..=0, TTL).unwrap().epoch, 2);
+    }
+
+    #[test]
+    fn test_file_backend_shared_between_instances() {
+        let path = temp_dir("lease-file").join("leases");
+        let a = FileLeaseBackend::new(&path);
+        let b = FileLeaseBackend::new(&path);
+
+        let first = a.acquire("worker a", 0..=1, TTL).unwrap();
+        let second = b.acquire("worker-b", 0..=1, TTL).unwrap();
+        assert_eq!((first.node_id, second.node_id), (0, 1));
+        assert!(b.acquire("worker-c", 0..=1, TTL).is_err());
+
+        let renewed = b.renew(&first, TTL).unwrap();
+        assert_eq!(renewed.owner, "worker a");
+        a.release(&first).unwrap();
+        assert_eq!(b.acquire("worker-c", 0..=1, TTL).unwrap().epoch, 2);
+    }
+
+    #[test]
+    fn test_file_backend_replaces_table_atomically() {
+        let dir = temp_dir("lease-atomic");
+        let path = dir.join("leases");
+        let backend = FileLeaseBackend::new(&path);
+        let lease = backend.acquire("a", 0..=0, TTL).unwrap();
+        backend.release(&lease).unwrap();
+
+        // Leftover of a crash between writing and renaming the temp file
+        std::fs::write(dir.join("leases.tmp"), "").unwrap();
+        assert_eq!(backend.acquire("b", 0..=0, TTL).unwrap().epoch, 2);
+        assert!(!dir.join("leases.tmp").exists());
+        assert!(dir.join("leases.lock").exists());
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("0 2 "));
+    }
+
+    #[test]
+    fn test_file_backend_rejects_bad_input() {
+        let path = temp_dir("lease-corrupt").join("leases");
+        let backend = FileLeaseBackend::new(&path);
+        assert!(matches!(
+            backend.acquire("two\nlines", 0..=0, TTL),
+            Err(LeaseError::Backend { .. })
+        ));
+
+        std::fs::write(&path, "not a lease\n").unwrap();
+        assert!(matches!(
+            backend.acquire("a", 0..=0, TTL),
+            Err(LeaseError::Backend { .. })
+        ));
+    }
+}
@@ -0,0 +1,428 @@
+//! Generator bound to a node lease that stops issuing IDs once the lease expires
+
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::generator::unix_time_ms;
+use crate::{SnowID, SnowIDConfig, SnowIDExtractor};
+
+use super::{LeaseError, NodeLease, NodeLeaseBackend};
+
+/// A `SnowID` generator whose node ID is leased from a `NodeLeaseBackend`
+///
+/// `try_generate` fails with `LeaseError::Expired` as soon as the lease lapses, so a
+/// paused or partitioned process cannot keep issuing IDs after its node was granted to
+/// another holder. Keep the lease alive with `renew` or `spawn_heartbeat`.
+#[derive(Debug)]
+pub struct LeasedSnowID<B: NodeLeaseBackend> {
+    generator: SnowID,
+    backend: B,
+    ttl: Duration,
+    lease: Mutex<NodeLease>,
+    expires_at_ms: AtomicU64,
+}
+
+impl<B: NodeLeaseBackend> LeasedSnowID<B> {
+    /// Lease a node ID in `range` for `ttl` and create a generator for it
+    ///
+    /// With node roles declared, only node IDs inside a role are requested
+    pub fn acquire(
+        backend: B,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+        config: SnowIDConfig,
+    ) -> Result<Self, LeaseError> {
+        let max = config.max_node_id();
+        if *range.end() > max {
+            return Err(LeaseError::OutOfRange {
+                node_id: *range.end(),
+                max,
+            });
+        }
+
+        let lease = Self::acquire_in_roles(&backend, owner, range, ttl, &config)?;
+        let generator = match SnowID::with_config(lease.node_id, config) {
+            Ok(generator) => generator,
+            Err(e) => {
+                // Hand the node back instead of blocking it until the TTL runs out
+                let _ = backend.release(&lease);
+                return Err(LeaseError::Generate(e));
+            }
+        };
+        Ok(Self {
+            generator,
+            backend,
+            ttl,
+            expires_at_ms: AtomicU64::new(lease.expires_at_ms),
+            lease: Mutex::new(lease),
+        })
+    }
+
+    /// Ask the backend for each part of `range` that lies inside a role, lowest first
+    fn acquire_in_roles(
+        backend: &B,
+        owner: &str,
+        range: RangeInclusive<u16>,
+        ttl: Duration,
+        config: &SnowIDConfig,
+    ) -> Result<NodeLease, LeaseError> {
+        if config.roles().is_empty() {
+            return backend.acquire(owner, range, ttl);
+        }
+        let mut parts: Vec<_> = config
+            .roles()
+            .iter()
+            .map(|role| *range.start().max(&role.first)..=*range.end().min(&role.last))
+            .filter(|part| !part.is_empty())
+            .collect();
+        parts.sort_by_key(|part| *part.start());
+
+        for part in parts {
+            match backend.acquire(owner, part, ttl) {
+                Err(LeaseError::Exhausted { .. }) => continue,
+                result => return result,
+            }
+        }
+        Err(LeaseError::Exhausted {
+            start: *range.start(),
+            end: *range.end(),
+        })
+    }
+
+    /// Generate a new ID, failing once the lease has expired
+    #[inline]
+    pub fn try_generate(&self) -> Result<u64, LeaseError> {
+        let expires_at_ms = self.expires_at_ms.load(Ordering::Acquire);
+        if unix_time_ms() >= expires_at_ms {
+            return Err(self.expired(expires_at_ms));
+        }
+
+        let id = self
+            .generator
+            .try_generate()
+            .map_err(LeaseError::Generate)?;
+        // The wait for a free sequence may have crossed the expiry. A new holder may start
+        // in the tick that contains the expiry, so that whole tick is off limits
+        let tick_end_ms = self
+            .generator
+            .extract
+            .timestamp_ms(id)
+            .saturating_add(self.generator.config.tick_ms());
+        if tick_end_ms > expires_at_ms {
+            return Err(self.expired(expires_at_ms));
+        }
+        Ok(id)
+    }
+
+    /// Extend the lease by its TTL from now
+    ///
+    /// On `LeaseError::Lost` the node ID belongs to someone else and this generator
+    /// stays expired for good
+    pub fn renew(&self) -> Result<(), LeaseError> {
+        let mut lease = self.lease.lock().unwrap_or_else(|e| e.into_inner());
+        let renewed = self.backend.renew(&lease, self.ttl)?;
+        self.expires_at_ms
+            .store(renewed.expires_at_ms, Ordering::Release);
+        *lease = renewed;
+        Ok(())
+    }
+
+    /// A copy of the current lease
+    pub fn lease(&self) -> NodeLease {
+        self.lease.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Fencing epoch of the lease; attach it to writes so stale holders can be rejected
+    #[inline]
+    pub fn fencing_epoch(&self) -> u64 {
+        self.lease().epoch
+    }
+
+    /// The leased node ID
+    #[inline]
+    pub fn node_id(&self) -> u16 {
+        self.generator.node_id
+    }
+
+    /// Extractor for the generator's layout
+    #[inline]
+    pub fn extractor(&self) -> &SnowIDExtractor {
+        &self.generator.extract
+    }
+
+    /// Recommended renewal interval: a third of the TTL, leaving room for two failed attempts
+    #[inline]
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.ttl / 3
+    }
+
+    fn expired(&self, expires_at_ms: u64) -> LeaseError {
+        LeaseError::Expired {
+            node_id: self.generator.node_id,
+            expires_at_ms,
+        }
+    }
+}
+
+impl<B: NodeLeaseBackend + Send + Sync + 'static> LeasedSnowID<B> {
+    /// Renew the lease every `heartbeat_interval` on a background thread
+    ///
+    /// The thread ends when the generator is dropped (returning `None`) or when a renewal
+    /// fails (returning the error); IDs then stop once the lease expires
+    pub fn spawn_heartbeat(self: &Arc<Self>) -> JoinHandle<Option<LeaseError>> {
+        let interval = self.heartbeat_interval();
+        let generator = Arc::downgrade(self);
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                let generator = generator.upgrade()?;
+                if let Err(e) = generator.renew() {
+                    return Some(e);
+                }
+            }
+        })
+    }
+}
+
+impl<B: NodeLeaseBackend> Drop for LeasedSnowID<B> {
+    fn drop(&mut self) {
+        let lease = self.lease.get_mut().unwrap_or_else(|e| e.into_inner());
+        // Best effort: an unreleased lease simply lapses after its TTL
+        let _ = self.backend.release(lease);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnowIDError;
+    use crate::node::MemoryLeaseBackend;
+
+    const SHORT_TTL: Duration = Duration::from_millis(150);
+
+    fn leased(
+        backend: &Arc<MemoryLeaseBackend>,
+        ttl: Duration,
+    ) -> LeasedSnowID<Arc<MemoryLeaseBackend>> {
+        LeasedSnowID::acquire(backend.clone(), "test", 0..=1, ttl, SnowIDConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_generates_with_leased_node() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let a = leased(&backend, Duration::from_secs(10));
+        let b = leased(&backend, Duration::from_secs(10));
+        assert_eq!((a.node_id(), b.node_id()), (0, 1));
+        assert_eq!(a.extractor().node(a.try_generate().unwrap()), 0);
+        assert_eq!(b.extractor().node(b.try_generate().unwrap()), 1);
+        assert_eq!(a.fencing_epoch(), 1);
+    }
+
+    #[test]
+    fn test_stops_when_lease_expires() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let generator = leased(&backend, SHORT_TTL);
+        assert!(generator.try_generate().is_ok());
+
+        thread::sleep(SHORT_TTL + Duration::from_millis(20));
+        assert!(matches!(
+            generator.try_generate(),
+            Err(LeaseError::Expired { node_id: 0, .. })
+        ));
+        assert!(matches!(generator.renew(), Err(LeaseError::Lost { .. })));
+    }
+
+    #[test]
+    fn test_renew_extends_lease() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let generator = leased(&backend, SHORT_TTL);
+        let before = generator.lease().expires_at_ms;
+        thread::sleep(Duration::from_millis(20));
+        generator.renew().unwrap();
+        assert!(generator.lease().expires_at_ms > before);
+        assert_eq!(generator.lease().epoch, 1);
+    }
+
+    #[test]
+    fn test_takeover_fences_old_holder() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let old = LeasedSnowID::acquire(
+            backend.clone(),
+            "old",
+            0..=0,
+            SHORT_TTL,
+            SnowIDConfig::default(),
+        )
+        .unwrap();
+        thread::sleep(SHORT_TTL + Duration::from_millis(20));
+
+        let new = LeasedSnowID::acquire(
+            backend.clone(),
+            "new",
+            0..=0,
+            SHORT_TTL,
+            SnowIDConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(new.fencing_epoch(), 2);
+        assert!(old.try_generate().is_err());
+        assert_eq!(
+            old.renew(),
+            Err(LeaseError::Lost {
+                node_id: 0,
+                epoch: 1
+            })
+        );
+
+        // Dropping the old holder must not release the new lease
+        drop(old);
+        assert!(backend.acquire("other", 0..=0, SHORT_TTL).is_err());
+        assert!(new.try_generate().is_ok());
+    }
+
+    #[test]
+    fn test_heartbeat_keeps_lease_alive() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let generator = Arc::new(leased(&backend, SHORT_TTL));
+        let heartbeat = generator.spawn_heartbeat();
+
+        thread::sleep(SHORT_TTL * 3);
+        assert!(generator.try_generate().is_ok());
+
+        drop(generator);
+        assert_eq!(heartbeat.join().unwrap(), None);
+        // Released on drop
+        assert_eq!(backend.acquire("next", 0..=0, SHORT_TTL).unwrap().epoch, 2);
+    }
+
+    /// Grants node 2000 whatever range is asked for, recording releases
+    #[derive(Default)]
+    struct OutOfLayoutBackend {
+        released: Mutex<Vec<NodeLease>>,
+    }
+
+    impl NodeLeaseBackend for OutOfLayoutBackend {
+        fn acquire(
+            &self,
+            owner: &str,
+            _range: RangeInclusive<u16>,
+            ttl: Duration,
+        ) -> Result<NodeLease, LeaseError> {
+            Ok(NodeLease {
+                node_id: 2000,
+                epoch: 1,
+                owner: owner.to_owned(),
+                expires_at_ms: unix_time_ms() + ttl.as_millis() as u64,
+            })
+        }
+
+        fn renew(&self, lease: &NodeLease, _ttl: Duration) -> Result<NodeLease, LeaseError> {
+            Ok(lease.clone())
+        }
+
+        fn release(&self, lease: &NodeLease) -> Result<(), LeaseError> {
+            self.released.lock().unwrap().push(lease.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_lease_released_when_generator_fails() {
+        let backend = Arc::new(OutOfLayoutBackend::default());
+        let result = LeasedSnowID::acquire(
+            backend.clone(),
+            "test",
+            0..=9,
+            Duration::from_secs(10),
+            SnowIDConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(LeaseError::Generate(SnowIDError::InvalidNodeId {
+                node_id: 2000,
+                ..
+            }))
+        ));
+        // The node is handed back instead of held until the TTL expires
+        let released = backend.released.lock().unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].node_id, 2000);
+    }
+
+    #[test]
+    fn test_acquire_only_requests_role_nodes() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let config = SnowIDConfig::builder()
+            .node_role("batch", 900..=901)
+            .unwrap()
+            .node_role("services", 0..=0)
+            .unwrap()
+            .build();
+        let acquire = || {
+            LeasedSnowID::acquire(
+                backend.clone(),
+                "test",
+                0..=1000,
+                Duration::from_secs(10),
+                config.clone(),
+            )
+        };
+        let held: Vec<_> = (0..3).map(|_| acquire().unwrap()).collect();
+        let nodes: Vec<u16> = held.iter().map(|generator| generator.node_id()).collect();
+        assert_eq!(nodes, [0, 900, 901]);
+        assert!(matches!(
+            acquire(),
+            Err(LeaseError::Exhausted {
+                start: 0,
+                end: 1000
+            })
+        ));
+        // Nodes outside every role were never leased
+        assert!(backend.acquire("other", 1..=1, SHORT_TTL).is_ok());
+    }
+
+    #[test]
+    fn test_tick_containing_expiry_is_rejected() {
+        let backend = Arc::new(MemoryLeaseBackend::new());
+        let config = SnowIDConfig::builder()
+            .js_safe(1000, 10, 12)
+            .unwrap()
+            .build();
+        let generator =
+            LeasedSnowID::acquire(backend, "test", 0..=0, Duration::from_secs(10), config).unwrap();
+        let id = generator.try_generate().unwrap();
+
+        // Expire just before the current tick ends: a new holder could start in this tick
+        let tick_start_ms = generator.extractor().timestamp_ms(id);
+        generator
+            .expires_at_ms
+            .store(tick_start_ms + 999, Ordering::Release);
+        assert!(matches!(
+            generator.try_generate(),
+            Err(LeaseError::Expired { node_id: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_range_checked_against_layout() {
+        let backend = MemoryLeaseBackend::new();
+        let result = LeasedSnowID::acquire(
+            backend,
+            "test",
+            0..=1024,
+            SHORT_TTL,
+            SnowIDConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(LeaseError::OutOfRange {
+                node_id: 1024,
+                max: 1023
+            })
+        ));
+    }
+}
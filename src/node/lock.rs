@@ -0,0 +1,152 @@
+//! Host-local node ID claiming with exclusive lock files
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::SnowIDConfig;
+
+use super::NodeIdError;
+
+/// Exclusive lock on `<dir>/<node>.lock`, held until dropped
+///
+/// The operating system releases the lock when the process exits, so a crashed
+/// process frees its node ID. Lock files are left in place for reuse.
+#[derive(Debug)]
+pub struct NodeLock {
+    node_id: u16,
+    path: PathBuf,
+    // Kept open to hold the lock
+    _file: File,
+}
+
+impl NodeLock {
+    /// Claim the first free node ID in `range`, creating `dir` if needed
+    ///
+    /// Skips node IDs outside every role when `config` declares node roles
+    pub fn claim(
+        dir: impl AsRef<Path>,
+        range: RangeInclusive<u16>,
+        config: &SnowIDConfig,
+    ) -> Result<Self, NodeIdError> {
+        let dir = dir.as_ref();
+        let max = config.max_node_id();
+        if *range.end() > max {
+            return Err(NodeIdError::OutOfRange {
+                node_id: *range.end() as u64,
+                max,
+            });
+        }
+        fs::create_dir_all(dir).map_err(|e| unavailable(dir, e))?;
+
+        // With roles declared, node IDs outside every role would be rejected by the generator
+        for node_id in range.clone().filter(|&node| config.node_in_roles(node)) {
+            if let Some(lock) = Self::try_lock(dir, node_id)? {
+                return Ok(lock);
+            }
+        }
+        Err(NodeIdError::Exhausted {
+            start: *range.start(),
+            end: *range.end(),
+        })
+    }
+
+    /// Try to lock one node ID, returning `None` if another holder has it
+    pub fn try_lock(dir: impl AsRef<Path>, node_id: u16) -> Result<Option<Self>, NodeIdError> {
+        let path = dir.as_ref().join(format!("{node_id}.lock"));
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| unavailable(&path, e))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(fs::TryLockError::WouldBlock) => return Ok(None),
+            Err(fs::TryLockError::Error(e)) => return Err(unavailable(&path, e)),
+        }
+
+        // Record the holder for operators; the lock itself is what matters
+        file.set_len(0)
+            .and_then(|()| writeln!(file, "{}", std::process::id()))
+            .map_err(|e| unavailable(&path, e))?;
+
+        Ok(Some(Self {
+            node_id,
+            path,
+            _file: file,
+        }))
+    }
+
+    /// The claimed node ID
+    #[inline]
+    pub fn node_id(&self) -> u16 {
+        self.node_id
+    }
+
+    /// Path of the held lock file
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn unavailable(path: &Path, e: std::io::Error) -> NodeIdError {
+    NodeIdError::Unavailable {
+        source: path.display().to_string(),
+        reason: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_utils::temp_dir;
+
+    #[test]
+    fn test_claims_distinct_nodes() {
+        let dir = temp_dir("lock-distinct");
+        let config = SnowIDConfig::default();
+        let a = NodeLock::claim(&dir, 0..=3, &config).unwrap();
+        let b = NodeLock::claim(&dir, 0..=3, &config).unwrap();
+        assert_eq!(a.node_id(), 0);
+        assert_eq!(b.node_id(), 1);
+        assert_eq!(a.path(), dir.join("0.lock"));
+
+        let contents = fs::read_to_string(a.path()).unwrap();
+        assert_eq!(contents.trim(), std::process::id().to_string());
+    }
+
+    #[test]
+    fn test_drop_releases_node() {
+        let dir = temp_dir("lock-release");
+        let config = SnowIDConfig::default();
+        let a = NodeLock::claim(&dir, 5..=5, &config).unwrap();
+        assert!(NodeLock::try_lock(&dir, 5).unwrap().is_none());
+        drop(a);
+        assert_eq!(NodeLock::claim(&dir, 5..=5, &config).unwrap().node_id(), 5);
+    }
+
+    #[test]
+    fn test_exhausted_and_out_of_range() {
+        let dir = temp_dir("lock-exhausted");
+        let config = SnowIDConfig::default();
+        let _held = [
+            NodeLock::claim(&dir, 1..=2, &config).unwrap(),
+            NodeLock::claim(&dir, 1..=2, &config).unwrap(),
+        ];
+        assert_eq!(
+            NodeLock::claim(&dir, 1..=2, &config).unwrap_err(),
+            NodeIdError::Exhausted { start: 1, end: 2 }
+        );
+        assert_eq!(
+            NodeLock::claim(&dir, 0..=1024, &config).unwrap_err(),
+            NodeIdError::OutOfRange {
+                node_id: 1024,
+                max: 1023
+            }
+        );
+    }
+}
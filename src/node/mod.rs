@@ -0,0 +1,22 @@
+//! Node ID discovery and allocation
+//!
+//! - `NodeIdProvider`: Works out the `node_id` for this process before creating a generator
+//! - Built-in providers: `StaticNodeId`, `EnvVar`, `StatefulSetOrdinal`, `HostnameHash`,
+//!   `MachineIdHash` and `PrivateIpv4`
+//! - `NodeLock`: Host-local claim of a free node ID via an exclusive lock file
+//! - `NodeLeaseBackend`: Cluster-wide TTL leases with fencing epochs, with in-memory and
+//!   file backends
+//! - `LeasedSnowID`: Generator that stops issuing IDs once its lease expires
+
+mod lease;
+mod leased;
+mod lock;
+mod provider;
+
+pub use lease::{FileLeaseBackend, LeaseError, MemoryLeaseBackend, NodeLease, NodeLeaseBackend};
+pub use leased::LeasedSnowID;
+pub use lock::NodeLock;
+pub use provider::{
+    EnvVar, HostnameHash, MachineIdHash, NodeIdError, NodeIdProvider, PrivateIpv4,
+    StatefulSetOrdinal, StaticNodeId,
+};
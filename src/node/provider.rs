@@ -0,0 +1,426 @@
+//! `NodeIdProvider` trait and built-in discovery strategies
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::path::PathBuf;
+
+use crate::SnowIDConfig;
+
+/// Default location of the systemd machine ID
+const MACHINE_ID_PATH: &str = "/etc/machine-id";
+
+/// Files consulted for the hostname when `HOSTNAME` is not set
+const HOSTNAME_PATHS: [&str; 2] = ["/etc/hostname", "/proc/sys/kernel/hostname"];
+
+/// Source of the node ID for a generator
+///
+/// Implementations must return a node ID within `config.max_node_id()`
+pub trait NodeIdProvider {
+    /// Work out the node ID for the given layout
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError>;
+}
+
+/// A fixed node ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticNodeId(pub u16);
+
+impl NodeIdProvider for StaticNodeId {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        check_range(self.0 as u64, config)
+    }
+}
+
+/// Node ID parsed from a decimal environment variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    name: String,
+}
+
+impl EnvVar {
+    /// Read the node ID from the named variable, e.g. `NODE_ID`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl NodeIdProvider for EnvVar {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        let value = std::env::var(&self.name).map_err(|_| NodeIdError::Unavailable {
+            source: format!("${}", self.name),
+            reason: "variable is not set or not valid UTF-8".to_owned(),
+        })?;
+        let node = value
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| NodeIdError::Invalid { value })?;
+        check_range(node, config)
+    }
+}
+
+/// Node ID from a Kubernetes StatefulSet pod ordinal, e.g. `web-3` -> 3
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatefulSetOrdinal {
+    hostname: Option<String>,
+}
+
+impl StatefulSetOrdinal {
+    /// Parse the ordinal from this host's name
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the ordinal from the given hostname instead of this host's
+    pub fn with_hostname(hostname: impl Into<String>) -> Self {
+        Self {
+            hostname: Some(hostname.into()),
+        }
+    }
+}
+
+impl NodeIdProvider for StatefulSetOrdinal {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        let hostname = resolve_hostname(&self.hostname)?;
+        // Pod hostnames may be fully qualified: web-3.web.default.svc.cluster.local
+        let pod = hostname.split('.').next().unwrap_or_default();
+        let ordinal = pod
+            .rsplit_once('-')
+            .and_then(|(_, ordinal)| ordinal.parse::<u64>().ok())
+            .ok_or_else(|| NodeIdError::Invalid {
+                value: hostname.clone(),
+            })?;
+        check_range(ordinal, config)
+    }
+}
+
+/// Node ID from a hash of the hostname, reduced to the layout's node range
+///
+/// Collisions are possible; prefer explicit assignment when nodes are few and known
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostnameHash {
+    hostname: Option<String>,
+}
+
+impl HostnameHash {
+    /// Hash this host's name
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash the given hostname instead of this host's
+    pub fn with_hostname(hostname: impl Into<String>) -> Self {
+        Self {
+            hostname: Some(hostname.into()),
+        }
+    }
+}
+
+impl NodeIdProvider for HostnameHash {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        let hostname = resolve_hostname(&self.hostname)?;
+        if hostname.is_empty() {
+            return Err(NodeIdError::Invalid { value: hostname });
+        }
+        Ok(reduce_hash(hostname.as_bytes(), config))
+    }
+}
+
+/// Node ID from a hash of the machine ID file, reduced to the layout's node range
+///
+/// Collisions are possible; prefer explicit assignment when nodes are few and known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineIdHash {
+    path: PathBuf,
+}
+
+impl MachineIdHash {
+    /// Hash `/etc/machine-id`
+    pub fn new() -> Self {
+        Self::with_path(MACHINE_ID_PATH)
+    }
+
+    /// Hash the contents of another machine ID file
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Default for MachineIdHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeIdProvider for MachineIdHash {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        let contents = fs::read_to_string(&self.path).map_err(|e| NodeIdError::Unavailable {
+            source: self.path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        let machine_id = contents.trim();
+        if machine_id.is_empty() {
+            return Err(NodeIdError::Invalid {
+                value: machine_id.to_owned(),
+            });
+        }
+        Ok(reduce_hash(machine_id.as_bytes(), config))
+    }
+}
+
+/// Node ID from the low bits of a private IPv4 address, as Sonyflake does
+///
+/// Unique as long as hosts share a subnet no larger than the node range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrivateIpv4 {
+    addr: Option<Ipv4Addr>,
+}
+
+impl PrivateIpv4 {
+    /// Use the address of the interface with the default route
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given address instead of discovering one
+    pub fn with_addr(addr: Ipv4Addr) -> Self {
+        Self { addr: Some(addr) }
+    }
+}
+
+impl NodeIdProvider for PrivateIpv4 {
+    fn node_id(&self, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+        let addr = match self.addr {
+            Some(addr) => addr,
+            None => local_ipv4()?,
+        };
+        if !addr.is_private() {
+            return Err(NodeIdError::Invalid {
+                value: addr.to_string(),
+            });
+        }
+        Ok((u32::from(addr) & config.max_node_id() as u32) as u16)
+    }
+}
+
+/// Find the local address of the default route (connecting UDP sends no packets)
+fn local_ipv4() -> Result<Ipv4Addr, NodeIdError> {
+    let unavailable = |e: std::io::Error| NodeIdError::Unavailable {
+        source: "local IPv4 address".to_owned(),
+        reason: e.to_string(),
+    };
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(unavailable)?;
+    socket
+        .connect((Ipv4Addr::new(10, 254, 254, 254), 1))
+        .map_err(unavailable)?;
+    match socket.local_addr().map_err(unavailable)? {
+        SocketAddr::V4(addr) => Ok(*addr.ip()),
+        SocketAddr::V6(addr) => Err(NodeIdError::Invalid {
+            value: addr.ip().to_string(),
+        }),
+    }
+}
+
+/// Use the explicit hostname, else `$HOSTNAME`, else the system hostname files
+fn resolve_hostname(explicit: &Option<String>) -> Result<String, NodeIdError> {
+    if let Some(hostname) = explicit {
+        return Ok(hostname.clone());
+    }
+    if let Ok(hostname) = std::env::var("HOSTNAME")
+        && !hostname.trim().is_empty()
+    {
+        return Ok(hostname.trim().to_owned());
+    }
+    HOSTNAME_PATHS
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|contents| contents.trim().to_owned())
+        .find(|hostname| !hostname.is_empty())
+        .ok_or_else(|| NodeIdError::Unavailable {
+            source: "hostname".to_owned(),
+            reason: "HOSTNAME is not set and no hostname file is readable".to_owned(),
+        })
+}
+
+/// FNV-1a hash reduced to `0..=max_node_id`
+fn reduce_hash(bytes: &[u8], config: &SnowIDConfig) -> u16 {
+    let hash = bytes.iter().fold(0xCBF2_9CE4_8422_2325u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    });
+    (hash % (config.max_node_id() as u64 + 1)) as u16
+}
+
+fn check_range(node_id: u64, config: &SnowIDConfig) -> Result<u16, NodeIdError> {
+    let max = config.max_node_id();
+    if node_id > max as u64 {
+        return Err(NodeIdError::OutOfRange { node_id, max });
+    }
+    Ok(node_id as u16)
+}
+
+/// Errors when working out a node ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeIdError {
+    /// The source (variable, file, network) could not be read
+    Unavailable { source: String, reason: String },
+    /// The source was read but does not contain a usable node ID
+    Invalid { value: String },
+    /// The node ID exceeds the layout's maximum
+    OutOfRange { node_id: u64, max: u16 },
+    /// Every node ID in the range is already claimed
+    Exhausted { start: u16, end: u16 },
+}
+
+impl fmt::Display for NodeIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeIdError::Unavailable { source, reason } => {
+                write!(f, "Node ID source {} is unavailable: {}", source, reason)
+            }
+            NodeIdError::Invalid { value } => {
+                write!(f, "No usable node ID in {:?}", value)
+            }
+            NodeIdError::OutOfRange { node_id, max } => write!(
+                f,
+                "Node ID {} is invalid. Maximum allowed value is {}",
+                node_id, max
+            ),
+            NodeIdError::Exhausted { start, end } => {
+                write!(f, "All node IDs in {}..={} are claimed", start, end)
+            }
+        }
+    }
+}
+
+impl Error for NodeIdError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SnowIDConfig {
+        SnowIDConfig::default()
+    }
+
+    #[test]
+    fn test_static() {
+        assert_eq!(StaticNodeId(7).node_id(&config()), Ok(7));
+        assert_eq!(
+            StaticNodeId(1024).node_id(&config()),
+            Err(NodeIdError::OutOfRange {
+                node_id: 1024,
+                max: 1023
+            })
+        );
+    }
+
+    #[test]
+    fn test_env_var_missing() {
+        let provider = EnvVar::new("SNOWID_TEST_UNSET_NODE_ID");
+        assert!(matches!(
+            provider.node_id(&config()),
+            Err(NodeIdError::Unavailable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_statefulset_ordinal() {
+        let ordinal = |host: &str| StatefulSetOrdinal::with_hostname(host).node_id(&config());
+        assert_eq!(ordinal("web-3"), Ok(3));
+        assert_eq!(ordinal("my-app-db-12"), Ok(12));
+        assert_eq!(ordinal("web-5.web.default.svc.cluster.local"), Ok(5));
+        assert_eq!(
+            ordinal("web"),
+            Err(NodeIdError::Invalid {
+                value: "web".to_owned()
+            })
+        );
+        assert!(matches!(
+            ordinal("web-2048"),
+            Err(NodeIdError::OutOfRange { node_id: 2048, .. })
+        ));
+    }
+
+    #[test]
+    fn test_hostname_hash_in_range_and_stable() {
+        let small = SnowIDConfig::builder().node_bits(6).unwrap().build();
+        for host in ["alpha", "beta", "gamma.example.com"] {
+            let provider = HostnameHash::with_hostname(host);
+            let node = provider.node_id(&small).unwrap();
+            assert!(node <= small.max_node_id());
+            assert_eq!(provider.node_id(&small), Ok(node));
+        }
+        assert!(matches!(
+            HostnameHash::with_hostname("").node_id(&small),
+            Err(NodeIdError::Invalid { .. })
+        ));
+        assert_ne!(
+            HostnameHash::with_hostname("alpha").node_id(&config()),
+            HostnameHash::with_hostname("beta").node_id(&config())
+        );
+    }
+
+    #[test]
+    fn test_machine_id_hash() {
+        let path = std::env::temp_dir().join(format!("snowid-machine-id-{}", std::process::id()));
+        fs::write(&path, "4c4c4544004d3510804bb4c04f4b3232\n").unwrap();
+        let node = MachineIdHash::with_path(&path).node_id(&config()).unwrap();
+        assert!(node <= config().max_node_id());
+
+        fs::write(&path, "\n").unwrap();
+        assert!(matches!(
+            MachineIdHash::with_path(&path).node_id(&config()),
+            Err(NodeIdError::Invalid { .. })
+        ));
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            MachineIdHash::with_path(&path).node_id(&config()),
+            Err(NodeIdError::Unavailable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_private_ipv4_low_bits() {
+        let ip = |a, b, c, d| PrivateIpv4::with_addr(Ipv4Addr::new(a, b, c, d));
+        // 10 node bits: low 10 bits of 10.0.5.7 = (5 & 0b11) << 8 | 7
+        assert_eq!(ip(10, 0, 5, 7).node_id(&config()), Ok(0x107));
+        assert_eq!(ip(192, 168, 1, 255).node_id(&config()), Ok(0x1FF));
+        assert_eq!(
+            ip(8, 8, 8, 8).node_id(&config()),
+            Err(NodeIdError::Invalid {
+                value: "8.8.8.8".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_snowid_from_provider() {
+        let generator =
+            crate::SnowID::from_provider(&StatefulSetOrdinal::with_hostname("web-3"), config())
+                .unwrap();
+        assert_eq!(generator.node_id, 3);
+        assert_eq!(generator.extract.node(generator.generate()), 3);
+
+        assert!(crate::SnowID::from_provider(&StaticNodeId(1024), config()).is_err());
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            NodeIdError::OutOfRange {
+                node_id: 1024,
+                max: 1023
+            }
+            .to_string(),
+            "Node ID 1024 is invalid. Maximum allowed value is 1023"
+        );
+        assert_eq!(
+            NodeIdError::Invalid {
+                value: "web".to_owned()
+            }
+            .to_string(),
+            "No usable node ID in \"web\""
+        );
+    }
+}
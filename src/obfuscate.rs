@@ -0,0 +1,183 @@
+//! Reversible keyed obfuscation of public IDs
+//!
+//! Public base62 IDs reveal creation time, node and per-millisecond volume to anyone who
+//! decomposes them. A keyed Feistel network permutes the 64-bit value so public IDs look
+//! random, while internal IDs stay sortable and `decode_public` recovers them exactly.
+//!
+//! This is obfuscation, not encryption: keep the key secret, but do not rely on it
+//! against a determined attacker with many known ID pairs.
+
+use std::fmt;
+
+use crate::base62::{self, DecodeError, MAX_LEN};
+
+/// Number of Feistel rounds
+const ROUNDS: usize = 8;
+
+/// Secret key for `obfuscate` / `encode_public`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ObfuscationKey {
+    round_keys: [u64; ROUNDS],
+}
+
+impl ObfuscationKey {
+    /// Derive round keys from a 128-bit secret
+    pub const fn new(secret: u128) -> Self {
+        let mut state = (secret as u64) ^ ((secret >> 64) as u64).rotate_left(32);
+        let mut round_keys = [0u64; ROUNDS];
+        let mut i = 0;
+        while i < ROUNDS {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            round_keys[i] = mix64(state ^ (secret >> 64) as u64);
+            i += 1;
+        }
+        Self { round_keys }
+    }
+
+    /// Derive round keys from 16 secret bytes
+    pub const fn from_bytes(secret: [u8; 16]) -> Self {
+        Self::new(u128::from_le_bytes(secret))
+    }
+}
+
+impl fmt::Debug for ObfuscationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ObfuscationKey(..)")
+    }
+}
+
+/// SplitMix64 finalizer
+#[inline(always)]
+const fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Feistel round function
+#[inline(always)]
+const fn round(half: u32, key: u64) -> u32 {
+    (mix64(half as u64 ^ key) >> 32) as u32
+}
+
+/// Permute an ID with the key (bijective on u64)
+#[inline]
+pub const fn obfuscate(id: u64, key: &ObfuscationKey) -> u64 {
+    let mut left = (id >> 32) as u32;
+    let mut right = id as u32;
+    let mut i = 0;
+    while i < ROUNDS {
+        let next = left ^ round(right, key.round_keys[i]);
+        left = right;
+        right = next;
+        i += 1;
+    }
+    ((left as u64) << 32) | right as u64
+}
+
+/// Reverse `obfuscate` with the same key
+#[inline]
+pub const fn deobfuscate(value: u64, key: &ObfuscationKey) -> u64 {
+    let mut left = (value >> 32) as u32;
+    let mut right = value as u32;
+    let mut i = ROUNDS;
+    while i > 0 {
+        i -= 1;
+        let prev = right ^ round(left, key.round_keys[i]);
+        right = left;
+        left = prev;
+    }
+    ((left as u64) << 32) | right as u64
+}
+
+/// Zero-allocation public encoding: obfuscated value as fixed-width base62
+#[inline]
+pub fn encode_public_array(id: u64, key: &ObfuscationKey) -> [u8; MAX_LEN] {
+    base62::encode_fixed_array(obfuscate(id, key))
+}
+
+/// Zero-allocation public encoding into caller-provided buffer
+#[inline]
+pub fn encode_public_into<'a>(
+    id: u64,
+    key: &ObfuscationKey,
+    buf: &'a mut [u8; MAX_LEN],
+) -> &'a str {
+    base62::encode_fixed_into(obfuscate(id, key), buf)
+}
+
+/// Public encoding with String allocation (convenience wrapper)
+#[inline]
+pub fn encode_public(id: u64, key: &ObfuscationKey) -> String {
+    base62::encode_fixed(obfuscate(id, key))
+}
+
+/// Decode a public ID back to the internal SnowID
+pub fn decode_public(encoded: &str, key: &ObfuscationKey) -> Result<u64, DecodeError> {
+    Ok(deobfuscate(base62::decode_fixed(encoded)?, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: ObfuscationKey = ObfuscationKey::new(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210);
+
+    #[test]
+    fn test_roundtrip() {
+        for id in [0u64, 1, 42, 151819733950271234, u64::MAX - 1, u64::MAX] {
+            assert_eq!(deobfuscate(obfuscate(id, &KEY), &KEY), id);
+            assert_eq!(decode_public(&encode_public(id, &KEY), &KEY).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_sequential_ids_look_unrelated() {
+        let base = 151819733950271234u64;
+        let a = obfuscate(base, &KEY);
+        let b = obfuscate(base + 1, &KEY);
+        assert_ne!(a >> 22, b >> 22, "timestamp bits should not be shared");
+        assert!((a ^ b).count_ones() > 16);
+    }
+
+    #[test]
+    fn test_key_matters() {
+        let other = ObfuscationKey::new(1);
+        let id = 151819733950271234u64;
+        assert_ne!(obfuscate(id, &KEY), obfuscate(id, &other));
+
+        let public = encode_public(id, &KEY);
+        assert_ne!(decode_public(&public, &other).unwrap(), id);
+    }
+
+    #[test]
+    fn test_from_bytes_matches_new() {
+        let secret = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        assert_eq!(
+            ObfuscationKey::from_bytes(secret.to_le_bytes()),
+            ObfuscationKey::new(secret)
+        );
+    }
+
+    #[test]
+    fn test_public_encoding_fixed_width() {
+        let mut buf = [0u8; MAX_LEN];
+        for id in [0u64, 1, u64::MAX] {
+            assert_eq!(encode_public(id, &KEY).len(), MAX_LEN);
+            assert_eq!(
+                encode_public_into(id, &KEY, &mut buf),
+                encode_public(id, &KEY)
+            );
+            assert_eq!(
+                &encode_public_array(id, &KEY),
+                encode_public(id, &KEY).as_bytes()
+            );
+        }
+        assert!(decode_public("abc", &KEY).is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_key() {
+        assert_eq!(format!("{KEY:?}"), "ObfuscationKey(..)");
+    }
+}
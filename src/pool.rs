@@ -0,0 +1,181 @@
+//! Multi-node generator pool for throughput beyond one node's sequence space
+//!
+//! One node issues at most `max_sequence_id() + 1` IDs per tick. `SnowIDPool` owns several
+//! node IDs and spreads calls across them; when a node's sequence is exhausted it moves on
+//! to the next node instead of sleeping, and only waits once every node is exhausted.
+//!
+//! IDs from a pool are unique and time-ordered across ticks, but IDs issued within the same
+//! tick by different nodes are not ordered by issue time.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::config::SnowIDConfig;
+use crate::error::SnowIDError;
+use crate::extractor::SnowIDExtractor;
+use crate::generator::SnowID;
+
+/// How a pool picks the node for the next ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolStrategy {
+    /// Cycle through the nodes in order
+    #[default]
+    RoundRobin,
+    /// Pick the node that has issued the fewest IDs
+    LeastUsed,
+}
+
+/// Generators for a set of node IDs sharing one layout
+#[derive(Debug)]
+pub struct SnowIDPool {
+    pub(crate) generators: Vec<SnowID>,
+    issued: Vec<AtomicU64>,
+    next: AtomicUsize,
+    strategy: PoolStrategy,
+    extract: SnowIDExtractor,
+}
+
+impl SnowIDPool {
+    /// Create a round-robin pool with default configuration
+    pub fn new(node_ids: impl IntoIterator<Item = u16>) -> Result<Self, PoolError> {
+        Self::with_config(node_ids, SnowIDConfig::default(), PoolStrategy::RoundRobin)
+    }
+
+    /// Create a pool with custom configuration and strategy
+    pub fn with_config(
+        node_ids: impl IntoIterator<Item = u16>,
+        config: SnowIDConfig,
+        strategy: PoolStrategy,
+    ) -> Result<Self, PoolError> {
+        let mut seen = HashSet::new();
+        let mut generators = Vec::new();
+        for node_id in node_ids {
+            if !seen.insert(node_id) {
+                return Err(PoolError::DuplicateNode { node_id });
+            }
+            generators.push(SnowID::with_config(node_id, config.clone()).map_err(PoolError::Node)?);
+        }
+        if generators.is_empty() {
+            return Err(PoolError::Empty);
+        }
+
+        Ok(Self {
+            issued: generators.iter().map(|_| AtomicU64::new(0)).collect(),
+            generators,
+            next: AtomicUsize::new(0),
+            strategy,
+            extract: config.extractor(),
+        })
+    }
+
+    /// Generate a new SnowID
+    ///
+    /// # Panics
+    /// Panics once the timestamp no longer fits the configured layout (see `try_generate`)
+    #[inline]
+    pub fn generate(&self) -> u64 {
+        match self.try_generate() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Generate a new SnowID, failing once the timestamp no longer fits the configured layout
+    pub fn try_generate(&self) -> Result<u64, SnowIDError> {
+        let start = self.pick();
+        let len = self.generators.len();
+        for offset in 0..len {
+            let index = (start + offset) % len;
+            if let Some(id) = self.generators[index].try_generate_nowait()? {
+                self.issued[index].fetch_add(1, Ordering::Relaxed);
+                return Ok(id);
+            }
+        }
+
+        // Every node is exhausted for this tick: wait on the preferred one
+        let id = self.generators[start].try_generate()?;
+        self.issued[start].fetch_add(1, Ordering::Relaxed);
+        Ok(id)
+    }
+
+    fn pick(&self) -> usize {
+        match self.strategy {
+            PoolStrategy::RoundRobin => {
+                self.next.fetch_add(1, Ordering::Relaxed) % self.generators.len()
+            }
+            PoolStrategy::LeastUsed => (0..self.issued.len())
+                .min_by_key(|&i| self.issued[i].load(Ordering::Relaxed))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Node IDs owned by the pool, in pick order
+    pub fn node_ids(&self) -> impl Iterator<Item = u16> + '_ {
+        self.generators.iter().map(|generator| generator.node_id)
+    }
+
+    /// Number of IDs issued by each node, in pick order
+    pub fn issued(&self) -> Vec<(u16, u64)> {
+        self.node_ids()
+            .zip(self.issued.iter().map(|n| n.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Number of nodes in the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Always false: a pool owns at least one node
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// The pool's strategy
+    #[inline]
+    pub fn strategy(&self) -> PoolStrategy {
+        self.strategy
+    }
+
+    /// Extractor for the pool's layout
+    #[inline]
+    pub fn extractor(&self) -> &SnowIDExtractor {
+        &self.extract
+    }
+}
+
+/// Errors when creating a `SnowIDPool`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolError {
+    /// No node IDs were given
+    Empty,
+    /// A node ID was given more than once
+    DuplicateNode { node_id: u16 },
+    /// A node ID is invalid for the layout
+    Node(SnowIDError),
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::Empty => write!(f, "Pool needs at least one node ID"),
+            PoolError::DuplicateNode { node_id } => {
+                write!(f, "Node ID {} appears more than once in the pool", node_id)
+            }
+            PoolError::Node(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PoolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PoolError::Node(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Stripe-style prefixed IDs such as `usr_2qPfVQh7Jw9`
+//!
+//! - `Prefix`: Validated prefix that encodes `<prefix>_<base62>` and decodes only its own IDs
+//! - `PrefixRegistry`: Set of prefixes that rejects duplicates and parses any registered ID
+//! - `PrefixedSnowID`: Generator handle from `SnowID::prefixed`
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::SnowID;
+use crate::base62::{self, DecodeError};
+
+/// Maximum prefix length in characters
+pub const MAX_PREFIX_LEN: usize = 16;
+
+/// Maximum size of a prefixed ID: prefix, separator and base62 body
+pub const MAX_LEN: usize = MAX_PREFIX_LEN + 1 + base62::MAX_LEN;
+
+/// Separator between prefix and base62 body
+const SEPARATOR: u8 = b'_';
+
+/// A validated ID prefix: 1-16 lowercase ASCII letters, digits or underscores, starting with a letter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Prefix {
+    value: String,
+}
+
+impl Prefix {
+    /// Validate and create a prefix
+    pub fn new(prefix: &str) -> Result<Self, PrefixError> {
+        if !Self::is_valid(prefix) {
+            return Err(PrefixError::InvalidPrefix {
+                prefix: prefix.to_owned(),
+            });
+        }
+        Ok(Self {
+            value: prefix.to_owned(),
+        })
+    }
+
+    fn is_valid(prefix: &str) -> bool {
+        let bytes = prefix.as_bytes();
+        !bytes.is_empty()
+            && bytes.len() <= MAX_PREFIX_LEN
+            && bytes[0].is_ascii_lowercase()
+            && bytes[bytes.len() - 1] != SEPARATOR
+            && bytes
+                .iter()
+                .all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == SEPARATOR)
+    }
+
+    /// The prefix without separator
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Zero-allocation encoding into caller-provided buffer
+    /// Returns a str slice of the encoded `<prefix>_<base62>` portion
+    pub fn encode_into<'a>(&self, id: u64, buf: &'a mut [u8; MAX_LEN]) -> &'a str {
+        let prefix_len = self.value.len();
+        buf[..prefix_len].copy_from_slice(self.value.as_bytes());
+        buf[prefix_len] = SEPARATOR;
+
+        let (body, body_len) = base62::encode_array(id);
+        let len = prefix_len + 1 + body_len;
+        buf[prefix_len + 1..len].copy_from_slice(&body[..body_len]);
+        // Prefix and base62 output are always valid ASCII
+        std::str::from_utf8(&buf[..len]).unwrap()
+    }
+
+    /// Encode as `<prefix>_<base62>` (allocates String)
+    pub fn encode(&self, id: u64) -> String {
+        let mut buf = [0u8; MAX_LEN];
+        self.encode_into(id, &mut buf).to_owned()
+    }
+
+    /// Decode a `<prefix>_<base62>` string, rejecting any other prefix
+    pub fn decode(&self, encoded: &str) -> Result<u64, PrefixError> {
+        let (found, body) = split(encoded);
+        if found != self.value {
+            return Err(PrefixError::UnexpectedPrefix {
+                expected: self.value.clone(),
+                found: found.to_owned(),
+            });
+        }
+        Ok(base62::decode(body)?)
+    }
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+/// Split at the last separator; base62 bodies never contain one
+fn split(encoded: &str) -> (&str, &str) {
+    encoded
+        .rsplit_once(SEPARATOR as char)
+        .unwrap_or(("", encoded))
+}
+
+/// Registry of known prefixes, rejecting invalid or duplicate registrations
+#[derive(Debug, Clone, Default)]
+pub struct PrefixRegistry {
+    prefixes: HashMap<String, Prefix>,
+}
+
+impl PrefixRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a prefix, failing if it is invalid or already registered
+    pub fn register(&mut self, prefix: &str) -> Result<Prefix, PrefixError> {
+        let prefix = Prefix::new(prefix)?;
+        if self.prefixes.contains_key(prefix.as_str()) {
+            return Err(PrefixError::DuplicatePrefix {
+                prefix: prefix.value,
+            });
+        }
+        self.prefixes.insert(prefix.value.clone(), prefix.clone());
+        Ok(prefix)
+    }
+
+    /// Look up a registered prefix
+    pub fn get(&self, prefix: &str) -> Option<&Prefix> {
+        self.prefixes.get(prefix)
+    }
+
+    /// Parse an ID carrying any registered prefix
+    pub fn parse(&self, encoded: &str) -> Result<(&Prefix, u64), PrefixError> {
+        let (found, body) = split(encoded);
+        let prefix = self
+            .prefixes
+            .get(found)
+            .ok_or_else(|| PrefixError::UnknownPrefix {
+                prefix: found.to_owned(),
+            })?;
+        Ok((prefix, base62::decode(body)?))
+    }
+}
+
+/// Generator handle producing prefixed IDs, created by `SnowID::prefixed`
+#[derive(Debug, Clone)]
+pub struct PrefixedSnowID<'a> {
+    generator: &'a SnowID,
+    prefix: Prefix,
+}
+
+impl<'a> PrefixedSnowID<'a> {
+    /// Wrap a generator with an already validated prefix
+    pub fn new(generator: &'a SnowID, prefix: Prefix) -> Self {
+        Self { generator, prefix }
+    }
+
+    /// The prefix of generated IDs
+    #[inline]
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    /// Generate a new prefixed ID into caller-provided buffer (zero-allocation)
+    #[inline]
+    pub fn generate_into<'b>(&self, buf: &'b mut [u8; MAX_LEN]) -> (&'b str, u64) {
+        let id = self.generator.generate();
+        (self.prefix.encode_into(id, buf), id)
+    }
+
+    /// Generate a new prefixed ID (allocates String)
+    pub fn generate(&self) -> String {
+        self.prefix.encode(self.generator.generate())
+    }
+
+    /// Generate a new prefixed ID with raw u64 value
+    pub fn generate_with_raw(&self) -> (String, u64) {
+        let id = self.generator.generate();
+        (self.prefix.encode(id), id)
+    }
+
+    /// Decode an ID carrying this handle's prefix
+    pub fn decode(&self, encoded: &str) -> Result<u64, PrefixError> {
+        self.prefix.decode(encoded)
+    }
+
+    /// Decompose an ID carrying this handle's prefix into its components
+    pub fn decompose(&self, encoded: &str) -> Result<(u64, u16, u16), PrefixError> {
+        let id = self.decode(encoded)?;
+        Ok(self.generator.extract.decompose(id))
+    }
+}
+
+/// Errors for prefix registration and prefixed ID parsing
+#[derive(Debug)]
+pub enum PrefixError {
+    /// Prefix is empty, too long, or contains unsupported characters
+    InvalidPrefix { prefix: String },
+    /// Prefix is already registered
+    DuplicatePrefix { prefix: String },
+    /// ID carries a different prefix than expected (empty if none)
+    UnexpectedPrefix { expected: String, found: String },
+    /// ID carries a prefix that is not registered (empty if none)
+    UnknownPrefix { prefix: String },
+    /// Base62 body could not be decoded
+    Decode(DecodeError),
+}
+
+impl fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixError::InvalidPrefix { prefix } => write!(
+                f,
+                "Prefix {:?} is invalid, expected 1-{} lowercase letters, digits or underscores",
+                prefix, MAX_PREFIX_LEN
+            ),
+            PrefixError::DuplicatePrefix { prefix } => {
+                write!(f, "Prefix {:?} is already registered", prefix)
+            }
+            PrefixError::UnexpectedPrefix { expected, found } => {
+                write!(f, "Expected prefix {:?}, found {:?}", expected, found)
+            }
+            PrefixError::UnknownPrefix { prefix } => {
+                write!(f, "Prefix {:?} is not registered", prefix)
+            }
+            PrefixError::Decode(e) => write!(f, "Prefixed ID decode error: {}", e),
+        }
+    }
+}
+
+impl Error for PrefixError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PrefixError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<DecodeError> for PrefixError {
+    fn from(err: DecodeError) -> Self {
+        PrefixError::Decode(err)
+    }
+}
@@ -0,0 +1,263 @@
+//! Serde helpers for SnowID values (requires the `serde` feature)
+//!
+//! Ready-made modules for `#[serde(with = "...")]`:
+//! - `number`: Serializes as a JSON number (u64)
+//! - `string`: Serializes as a decimal string, safe for JavaScript clients
+//! - `base62`: Serializes as a base62 string
+//!
+//! In human-readable formats (JSON, YAML, ...) all three are lenient and accept both numbers
+//! and strings. Compact formats such as bincode and postcard cannot tell the two apart, so
+//! there each helper reads back exactly what it wrote: `number` a u64, `string` and `base62`
+//! a string.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "snowid::serde::string")]
+//!     id: u64,
+//! }
+//! ```
+
+use std::fmt;
+
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::ser::Serializer;
+
+/// How string input is interpreted during deserialization
+#[derive(Debug, Clone, Copy)]
+enum StringFormat {
+    Decimal,
+    Base62,
+}
+
+/// Visitor accepting unsigned/signed integers and strings
+struct IdVisitor(StringFormat);
+
+impl<'de> Visitor<'de> for IdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            StringFormat::Decimal => write!(f, "a SnowID as a u64 number or decimal string"),
+            StringFormat::Base62 => write!(f, "a SnowID as a u64 number or base62 string"),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<u64, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<u64, E> {
+        u64::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<u64, E> {
+        match self.0 {
+            StringFormat::Decimal => v
+                .parse::<u64>()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self)),
+            StringFormat::Base62 => crate::base62::decode(v).map_err(E::custom),
+        }
+    }
+}
+
+/// Accept a number or a string in human-readable formats, otherwise read the written form
+#[inline]
+fn deserialize_with<'de, D>(
+    deserializer: D,
+    format: StringFormat,
+    written_as_str: bool,
+) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let visitor = IdVisitor(format);
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else if written_as_str {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_u64(visitor)
+    }
+}
+
+/// Serialize and deserialize as a u64 number
+///
+/// Human-readable formats also accept a decimal string
+pub mod number {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(*id)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserialize_with(deserializer, StringFormat::Decimal, false)
+    }
+}
+
+/// Serialize as a decimal string, deserialize from a number or decimal string
+pub mod string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut buf = [0u8; 20];
+        serializer.serialize_str(format_decimal(*id, &mut buf))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserialize_with(deserializer, StringFormat::Decimal, true)
+    }
+
+    /// Zero-allocation decimal formatting (u64::MAX has 20 digits)
+    fn format_decimal(mut id: u64, buf: &mut [u8; 20]) -> &str {
+        let mut pos = buf.len();
+        loop {
+            pos -= 1;
+            buf[pos] = b'0' + (id % 10) as u8;
+            id /= 10;
+            if id == 0 {
+                break;
+            }
+        }
+        // Decimal digits are always valid ASCII
+        std::str::from_utf8(&buf[pos..]).unwrap()
+    }
+}
+
+/// Serialize as a base62 string, deserialize from a number or base62 string
+pub mod base62 {
+    use super::*;
+    use crate::base62::{MAX_LEN, encode_into};
+
+    pub fn serialize<S: Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut buf = [0u8; MAX_LEN];
+        serializer.serialize_str(encode_into(*id, &mut buf))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserialize_with(deserializer, StringFormat::Base62, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsNumber {
+        #[serde(with = "crate::serde::number")]
+        id: u64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsString {
+        #[serde(with = "crate::serde::string")]
+        id: u64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsBase62 {
+        #[serde(with = "crate::serde::base62")]
+        id: u64,
+    }
+
+    #[test]
+    fn test_number_roundtrip() {
+        let v = AsNumber { id: u64::MAX };
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, r#"{"id":18446744073709551615}"#);
+        assert_eq!(serde_json::from_str::<AsNumber>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn test_string_roundtrip() {
+        for id in [0u64, 7, 151819733950271234, u64::MAX] {
+            let v = AsString { id };
+            let json = serde_json::to_string(&v).unwrap();
+            assert_eq!(json, format!(r#"{{"id":"{id}"}}"#));
+            assert_eq!(serde_json::from_str::<AsString>(&json).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn test_base62_roundtrip() {
+        let id = 151819733950271234u64;
+        let v = AsBase62 { id };
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, format!(r#"{{"id":"{}"}}"#, crate::base62::encode(id)));
+        assert_eq!(serde_json::from_str::<AsBase62>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn test_lenient_deserialize() {
+        let s: AsString = serde_json::from_str(r#"{"id":42}"#).unwrap();
+        assert_eq!(s.id, 42);
+
+        let b: AsBase62 = serde_json::from_str(r#"{"id":42}"#).unwrap();
+        assert_eq!(b.id, 42);
+
+        let n: AsNumber = serde_json::from_str(r#"{"id":"42"}"#).unwrap();
+        assert_eq!(n.id, 42);
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        assert!(serde_json::from_str::<AsString>(r#"{"id":"abc"}"#).is_err());
+        assert!(serde_json::from_str::<AsString>(r#"{"id":-1}"#).is_err());
+        assert!(serde_json::from_str::<AsString>(r#"{"id":"18446744073709551616"}"#).is_err());
+        assert!(serde_json::from_str::<AsBase62>(r#"{"id":"abc!"}"#).is_err());
+        assert!(serde_json::from_str::<AsNumber>(r#"{"id":1.5}"#).is_err());
+        assert!(serde_json::from_str::<AsNumber>(r#"{"id":-1}"#).is_err());
+    }
+
+    #[test]
+    fn test_compact_formats_read_the_written_form() {
+        use ::serde::de::value::{Error, StrDeserializer, U64Deserializer};
+        use ::serde::de::{IntoDeserializer, Visitor};
+
+        // A deserializer that only supports typed requests, like bincode or postcard
+        struct TypedOnly<D>(D);
+
+        impl<'de, D: ::serde::Deserializer<'de, Error = Error>> ::serde::Deserializer<'de>
+            for TypedOnly<D>
+        {
+            type Error = Error;
+
+            fn is_human_readable(&self) -> bool {
+                false
+            }
+
+            fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+                Err(::serde::de::Error::custom(
+                    "deserialize_any is not supported",
+                ))
+            }
+
+            fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.0.deserialize_u64(visitor)
+            }
+
+            fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.0.deserialize_str(visitor)
+            }
+
+            ::serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u128 f32 f64 char string
+                bytes byte_buf option unit unit_struct newtype_struct seq tuple
+                tuple_struct map struct enum identifier ignored_any
+            }
+        }
+
+        fn text(s: &str) -> TypedOnly<StrDeserializer<'_, Error>> {
+            TypedOnly(s.into_deserializer())
+        }
+        let number = TypedOnly::<U64Deserializer<Error>>(42u64.into_deserializer());
+        let encoded = crate::base62::encode(42);
+        assert_eq!(crate::serde::number::deserialize(number), Ok(42));
+        assert_eq!(crate::serde::string::deserialize(text("42")), Ok(42));
+        assert_eq!(crate::serde::base62::deserialize(text(&encoded)), Ok(42));
+    }
+}
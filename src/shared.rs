@@ -0,0 +1,234 @@
+//! Cross-process shared generator backed by a memory-mapped state file
+//!
+//! Several processes on one machine (e.g. short-lived CLI invocations) can share a single
+//! node ID through `SharedSnowID`. The packed timestamp/sequence state that `SnowID` keeps in
+//! an `AtomicU64` lives in a memory-mapped file instead, and every process updates it with the
+//! same compare-and-swap, so generating an ID costs no lock and no system call.
+//!
+//! File layout (72 bytes):
+//! - `0..8`: magic `SNOWIDSH`
+//! - `8..40`: node and layout in the `SnowIDSnapshot` binary form, checked on open
+//! - `64..72`: packed state, native endian, only ever accessed atomically
+//!
+//! The state reaches disk through the page cache, so it survives process crashes but is not
+//! synced on every ID. Requires the `mmap` feature.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+
+use memmap2::MmapRaw;
+
+use crate::error::SnowIDError;
+use crate::snapshot::{SnapshotError, SnowIDSnapshot};
+use crate::{SnowID, SnowIDConfig, SnowIDExtractor};
+
+/// Identifies a shared state file
+const MAGIC: &[u8; 8] = b"SNOWIDSH";
+
+/// Offset of the node and layout header
+const HEADER_OFFSET: usize = MAGIC.len();
+
+/// Offset of the state word; a separate cache line from the read-only header
+const STATE_OFFSET: usize = 64;
+
+/// Size of the state file in bytes
+const FILE_LEN: usize = STATE_OFFSET + 8;
+
+/// Generator whose state is shared with other processes through a memory-mapped file
+#[derive(Debug)]
+pub struct SharedSnowID {
+    // Computes IDs; its own in-memory state is unused
+    generator: SnowID,
+    map: MmapRaw,
+    path: PathBuf,
+}
+
+impl SharedSnowID {
+    /// Open (or create) the state file for `node_id` with default configuration
+    pub fn open(path: impl AsRef<Path>, node_id: u16) -> Result<Self, SharedError> {
+        Self::open_with_config(path, node_id, SnowIDConfig::default())
+    }
+
+    /// Open (or create) the state file for `node_id` with custom configuration
+    ///
+    /// Fails with `NodeMismatch` or a layout error if the file belongs to another node or layout
+    pub fn open_with_config(
+        path: impl AsRef<Path>,
+        node_id: u16,
+        config: SnowIDConfig,
+    ) -> Result<Self, SharedError> {
+        let path = path.as_ref().to_path_buf();
+        let generator = SnowID::with_config(node_id, config).map_err(SharedError::Generate)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+
+        // Only creating or checking the header is locked; generation is lock-free
+        file.lock().map_err(|e| io_error(&path, e))?;
+        let prepared = prepare(&file, &path, &generator);
+        // Unlocking only fails if the handle is invalid; closing it would release the lock too
+        let _ = file.unlock();
+        prepared?;
+
+        let map = MmapRaw::map_raw(&file).map_err(|e| io_error(&path, e))?;
+        if map.len() < FILE_LEN {
+            return Err(invalid_file(&path));
+        }
+        Ok(Self {
+            generator,
+            map,
+            path,
+        })
+    }
+
+    /// Generate a new SnowID
+    ///
+    /// # Panics
+    /// Panics once the timestamp no longer fits the layout
+    pub fn generate(&self) -> u64 {
+        match self.try_generate() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Generate a new SnowID unique across all processes sharing the state file
+    pub fn try_generate(&self) -> Result<u64, SharedError> {
+        match self.generator.try_generate_on(self.state(), || true) {
+            Ok(Some(id)) => Ok(id),
+            Ok(None) => unreachable!("new ticks are always allowed"),
+            Err(e) => Err(SharedError::Generate(e)),
+        }
+    }
+
+    /// The mapped state word
+    #[allow(unsafe_code)]
+    fn state(&self) -> &AtomicU64 {
+        let ptr = self
+            .map
+            .as_mut_ptr()
+            .wrapping_add(STATE_OFFSET)
+            .cast::<u64>();
+        // SAFETY: the mapping is page aligned, at least FILE_LEN long (checked on open) and
+        // lives as long as `self`, so the word is aligned, in bounds and outlives the borrow.
+        // Every process sharing the file only accesses it atomically.
+        unsafe { AtomicU64::from_ptr(ptr) }
+    }
+
+    /// The shared node ID
+    #[inline]
+    pub fn node_id(&self) -> u16 {
+        self.generator.node_id
+    }
+
+    /// Path of the state file
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Extractor for the generator's layout
+    #[inline]
+    pub fn extractor(&self) -> &SnowIDExtractor {
+        &self.generator.extract
+    }
+}
+
+/// Write the header of a new state file, or check an existing one belongs to `generator`
+fn prepare(mut file: &File, path: &Path, generator: &SnowID) -> Result<(), SharedError> {
+    let len = file.metadata().map_err(|e| io_error(path, e))?.len();
+    if len == 0 {
+        let mut bytes = [0u8; FILE_LEN];
+        bytes[..HEADER_OFFSET].copy_from_slice(MAGIC);
+        bytes[HEADER_OFFSET..HEADER_OFFSET + SnowIDSnapshot::LEN]
+            .copy_from_slice(&generator.snapshot().to_bytes());
+        file.write_all(&bytes)
+            .and_then(|()| file.sync_all())
+            .map_err(|e| io_error(path, e))?;
+        return Ok(());
+    }
+    if len != FILE_LEN as u64 {
+        return Err(invalid_file(path));
+    }
+
+    let mut header = [0u8; HEADER_OFFSET + SnowIDSnapshot::LEN];
+    file.read_exact(&mut header)
+        .map_err(|e| io_error(path, e))?;
+    if &header[..HEADER_OFFSET] != MAGIC {
+        return Err(invalid_file(path));
+    }
+    let stored =
+        SnowIDSnapshot::from_bytes(&header[HEADER_OFFSET..]).map_err(SharedError::Snapshot)?;
+    if stored.node_id != generator.node_id {
+        return Err(SharedError::NodeMismatch {
+            expected: generator.node_id,
+            found: stored.node_id,
+        });
+    }
+    if !stored.matches_layout(&generator.config) {
+        return Err(SharedError::Snapshot(SnapshotError::LayoutMismatch));
+    }
+    Ok(())
+}
+
+fn invalid_file(path: &Path) -> SharedError {
+    SharedError::Io {
+        path: path.to_path_buf(),
+        reason: "not a shared SnowID state file".to_owned(),
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> SharedError {
+    SharedError::Io {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    }
+}
+
+/// Errors from a shared generator
+#[derive(Debug, Clone, PartialEq)]
+pub enum SharedError {
+    /// The state file could not be opened, locked or mapped, or is not a state file
+    Io { path: PathBuf, reason: String },
+    /// The state file belongs to another node ID
+    NodeMismatch { expected: u16, found: u16 },
+    /// The state file is corrupted or belongs to another layout
+    Snapshot(SnapshotError),
+    /// The generator failed
+    Generate(SnowIDError),
+}
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SharedError::Io { path, reason } => {
+                write!(f, "Shared state {}: {}", path.display(), reason)
+            }
+            SharedError::NodeMismatch { expected, found } => write!(
+                f,
+                "Shared state belongs to node {}, expected node {}",
+                found, expected
+            ),
+            SharedError::Snapshot(e) => write!(f, "Shared state: {}", e),
+            SharedError::Generate(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for SharedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SharedError::Snapshot(e) => Some(e),
+            SharedError::Generate(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Generator state export and import for graceful handoff
+//!
+//! `SnowID::snapshot` captures the last issued timestamp and sequence together with the
+//! node and layout. `SnowID::restore` continues strictly after it, so a replacement process
+//! takes over the node with no gap and no overlap. Snapshots travel as 32 bytes
+//! (`to_bytes` / `from_bytes`) or, with the `serde` feature, as any serde format.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::config::{SnowIDConfig, SnowIDConfigError};
+
+/// Version tag of the binary snapshot format
+const FORMAT_VERSION: u8 = 1;
+
+/// Total bits of every `SnowIDConfigBuilder::js_safe` layout
+const JS_SAFE_TOTAL_BITS: u16 = 53;
+
+/// Last issued position of a generator, with the node and layout it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowIDSnapshot {
+    /// Node ID of the generator
+    pub node_id: u16,
+    /// Timestamp of the last issued ID, in ticks since the epoch
+    pub timestamp: u64,
+    /// Sequence of the last issued ID
+    pub sequence: u16,
+    /// Layout: timestamp bits
+    pub timestamp_bits: u8,
+    /// Layout: node bits
+    pub node_bits: u8,
+    /// Layout: sequence bits
+    pub sequence_bits: u8,
+    /// Layout: epoch in Unix milliseconds
+    pub epoch: u64,
+    /// Layout: tick duration in milliseconds
+    pub tick_ms: u64,
+}
+
+impl SnowIDSnapshot {
+    /// Size of the binary form in bytes
+    pub const LEN: usize = 32;
+
+    /// Recreate the configuration described by the snapshot's layout
+    ///
+    /// Only the bit layout, epoch and tick are restored; spin settings are defaults and
+    /// node roles are not part of a snapshot
+    pub fn config(&self) -> Result<SnowIDConfig, SnapshotError> {
+        let builder = SnowIDConfig::builder().epoch(self.epoch);
+        // js_safe layouts span exactly 53 bits; the timestamp width alone is ambiguous,
+        // since js_safe with 11 node + sequence bits also has 42 timestamp bits
+        let total_bits =
+            self.timestamp_bits as u16 + self.node_bits as u16 + self.sequence_bits as u16;
+        let standard = total_bits != JS_SAFE_TOTAL_BITS;
+        let builder = if standard {
+            builder.node_bits(self.node_bits)
+        } else {
+            builder.js_safe(self.tick_ms, self.node_bits, self.sequence_bits)
+        }
+        .map_err(SnapshotError::InvalidLayout)?;
+
+        let config = builder.build();
+        if !self.matches_layout(&config) {
+            return Err(SnapshotError::LayoutMismatch);
+        }
+        Ok(config)
+    }
+
+    /// Whether the snapshot was taken from a generator with this layout
+    pub fn matches_layout(&self, config: &SnowIDConfig) -> bool {
+        self.timestamp_bits == config.timestamp_bits()
+            && self.node_bits == config.node_bits()
+            && self.sequence_bits == config.sequence_bits()
+            && self.epoch == config.epoch()
+            && self.tick_ms == config.tick_ms()
+    }
+
+    /// Encode as 32 bytes: version, then big-endian fields in declaration order
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0] = FORMAT_VERSION;
+        bytes[1..3].copy_from_slice(&self.node_id.to_be_bytes());
+        bytes[3..11].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[11..13].copy_from_slice(&self.sequence.to_be_bytes());
+        bytes[13] = self.timestamp_bits;
+        bytes[14] = self.node_bits;
+        bytes[15] = self.sequence_bits;
+        bytes[16..24].copy_from_slice(&self.epoch.to_be_bytes());
+        bytes[24..32].copy_from_slice(&self.tick_ms.to_be_bytes());
+        bytes
+    }
+
+    /// Decode the 32-byte form produced by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let bytes: &[u8; Self::LEN] = bytes
+            .try_into()
+            .map_err(|_| SnapshotError::InvalidLength { len: bytes.len() })?;
+        if bytes[0] != FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion { version: bytes[0] });
+        }
+
+        let u16_at = |i: usize| u16::from_be_bytes([bytes[i], bytes[i + 1]]);
+        let u64_at = |i: usize| u64::from_be_bytes(bytes[i..i + 8].try_into().unwrap());
+        Ok(Self {
+            node_id: u16_at(1),
+            timestamp: u64_at(3),
+            sequence: u16_at(11),
+            timestamp_bits: bytes[13],
+            node_bits: bytes[14],
+            sequence_bits: bytes[15],
+            epoch: u64_at(16),
+            tick_ms: u64_at(24),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for SnowIDSnapshot {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use ::serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("SnowIDSnapshot", 8)?;
+        s.serialize_field("node_id", &self.node_id)?;
+        s.serialize_field("timestamp", &self.timestamp)?;
+        s.serialize_field("sequence", &self.sequence)?;
+        s.serialize_field("timestamp_bits", &self.timestamp_bits)?;
+        s.serialize_field("node_bits", &self.node_bits)?;
+        s.serialize_field("sequence_bits", &self.sequence_bits)?;
+        s.serialize_field("epoch", &self.epoch)?;
+        s.serialize_field("tick_ms", &self.tick_ms)?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for SnowIDSnapshot {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use ::serde::de::{self, MapAccess, SeqAccess, Visitor};
+
+        const FIELDS: &[&str] = &[
+            "node_id",
+            "timestamp",
+            "sequence",
+            "timestamp_bits",
+            "node_bits",
+            "sequence_bits",
+            "epoch",
+            "tick_ms",
+        ];
+
+        struct SnapshotVisitor;
+
+        impl<'de> Visitor<'de> for SnapshotVisitor {
+            type Value = SnowIDSnapshot;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a SnowIDSnapshot")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut values = [None::<u64>; 8];
+                while let Some(key) = map.next_key::<String>()? {
+                    let index = FIELDS
+                        .iter()
+                        .position(|field| *field == key.as_str())
+                        .ok_or_else(|| de::Error::unknown_field(&key, FIELDS))?;
+                    if values[index].is_some() {
+                        return Err(de::Error::duplicate_field(FIELDS[index]));
+                    }
+                    values[index] = Some(map.next_value()?);
+                }
+
+                let field = |index: usize| {
+                    values[index].ok_or_else(|| de::Error::missing_field(FIELDS[index]))
+                };
+                let narrow = |index: usize, max: u64| {
+                    let value = field(index)?;
+                    if value > max {
+                        return Err(de::Error::invalid_value(
+                            de::Unexpected::Unsigned(value),
+                            &FIELDS[index],
+                        ));
+                    }
+                    Ok(value)
+                };
+                Ok(SnowIDSnapshot {
+                    node_id: narrow(0, u16::MAX as u64)? as u16,
+                    timestamp: field(1)?,
+                    sequence: narrow(2, u16::MAX as u64)? as u16,
+                    timestamp_bits: narrow(3, u8::MAX as u64)? as u8,
+                    node_bits: narrow(4, u8::MAX as u64)? as u8,
+                    sequence_bits: narrow(5, u8::MAX as u64)? as u8,
+                    epoch: field(6)?,
+                    tick_ms: field(7)?,
+                })
+            }
+
+            // Non-self-describing formats (bincode, postcard) send structs as sequences
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let missing = |index: usize| de::Error::invalid_length(index, &"8 fields");
+                Ok(SnowIDSnapshot {
+                    node_id: seq.next_element()?.ok_or_else(|| missing(0))?,
+                    timestamp: seq.next_element()?.ok_or_else(|| missing(1))?,
+                    sequence: seq.next_element()?.ok_or_else(|| missing(2))?,
+                    timestamp_bits: seq.next_element()?.ok_or_else(|| missing(3))?,
+                    node_bits: seq.next_element()?.ok_or_else(|| missing(4))?,
+                    sequence_bits: seq.next_element()?.ok_or_else(|| missing(5))?,
+                    epoch: seq.next_element()?.ok_or_else(|| missing(6))?,
+                    tick_ms: seq.next_element()?.ok_or_else(|| missing(7))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("SnowIDSnapshot", FIELDS, SnapshotVisitor)
+    }
+}
+
+/// Errors when decoding or restoring a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    /// Binary snapshot has the wrong number of bytes
+    InvalidLength { len: usize },
+    /// Binary snapshot was written by an unknown format version
+    UnsupportedVersion { version: u8 },
+    /// Snapshot layout cannot be built
+    InvalidLayout(SnowIDConfigError),
+    /// Snapshot layout differs from the configuration to restore into
+    LayoutMismatch,
+    /// The configuration declares node roles and the snapshot's node is outside all of them
+    NodeOutsideRoles { node_id: u16 },
+    /// Node, timestamp or sequence exceed the layout
+    InvalidState {
+        node_id: u16,
+        timestamp: u64,
+        sequence: u16,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::InvalidLength { len } => write!(
+                f,
+                "Invalid snapshot length {}, expected {} bytes",
+                len,
+                SnowIDSnapshot::LEN
+            ),
+            SnapshotError::UnsupportedVersion { version } => {
+                write!(f, "Unsupported snapshot format version {}", version)
+            }
+            SnapshotError::InvalidLayout(e) => write!(f, "Invalid snapshot layout: {}", e),
+            SnapshotError::LayoutMismatch => {
+                write!(f, "Snapshot layout differs from the configuration")
+            }
+            SnapshotError::NodeOutsideRoles { node_id } => {
+                write!(
+                    f,
+                    "Snapshot node {} is outside every declared role",
+                    node_id
+                )
+            }
+            SnapshotError::InvalidState {
+                node_id,
+                timestamp,
+                sequence,
+            } => write!(
+                f,
+                "Snapshot state (node {}, timestamp {}, sequence {}) exceeds the layout",
+                node_id, timestamp, sequence
+            ),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::InvalidLayout(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -18,7 +18,7 @@ fn test_base62_encoding_decoding() {
     fn test_base62_generator_consistency() {
         // Create a generator with a custom config
         let config = SnowIDConfig::default();
-        let generator = SnowID::with_config(42, config).unwrap();
+        let generator = SnowID::with_config(42, config.clone()).unwrap();
 
         // Generate both regular and base62 IDs with the same generator
         let regular_id = generator.generate();
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::test_utils::temp_dir;
+    use crate::*;
+
+    #[test]
+    fn test_claim_local_distinct_generators() {
+        let dir = temp_dir("claim-distinct");
+        let a = SnowID::claim_local(&dir, 10..=12).unwrap();
+        let b = SnowID::claim_local(&dir, 10..=12).unwrap();
+        assert_eq!(a.node_id, 10);
+        assert_eq!(b.node_id, 11);
+        assert_eq!(a.extract.node(a.generate()), 10);
+        assert_eq!(b.extract.node(b.generate()), 11);
+        assert_eq!(a.node_lock().unwrap().path(), dir.join("10.lock"));
+        assert!(SnowID::new(1).unwrap().node_lock().is_none());
+    }
+
+    #[test]
+    fn test_claim_local_released_on_drop() {
+        let dir = temp_dir("claim-drop");
+        let a = SnowID::claim_local(&dir, 0..=0).unwrap();
+        assert!(matches!(
+            SnowID::claim_local(&dir, 0..=0),
+            Err(node::NodeIdError::Exhausted { start: 0, end: 0 })
+        ));
+        drop(a);
+        assert_eq!(SnowID::claim_local(&dir, 0..=0).unwrap().node_id, 0);
+    }
+
+    #[test]
+    fn test_claim_local_with_config_range_checked() {
+        let dir = temp_dir("claim-config");
+        let config = SnowIDConfig::builder().node_bits(6).unwrap().build();
+        assert!(matches!(
+            SnowID::claim_local_with_config(&dir, 0..=64, config.clone()),
+            Err(node::NodeIdError::OutOfRange {
+                node_id: 64,
+                max: 63
+            })
+        ));
+        let generator = SnowID::claim_local_with_config(&dir, 60..=63, config).unwrap();
+        assert_eq!(generator.node_id, 60);
+    }
+
+    #[test]
+    fn test_claim_local_only_tries_role_nodes() {
+        let dir = temp_dir("claim-roles");
+        let config = SnowIDConfig::builder()
+            .node_role("workers", 5..=6)
+            .unwrap()
+            .build();
+        let a = SnowID::claim_local_with_config(&dir, 0..=9, config.clone()).unwrap();
+        let b = SnowID::claim_local_with_config(&dir, 0..=9, config.clone()).unwrap();
+        assert_eq!((a.node_id, b.node_id), (5, 6));
+        assert!(matches!(
+            SnowID::claim_local_with_config(&dir, 0..=9, config),
+            Err(node::NodeIdError::Exhausted { start: 0, end: 9 })
+        ));
+    }
+}
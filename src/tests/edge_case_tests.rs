@@ -15,7 +15,7 @@ fn test_min_node_bits_config() {
         assert_eq!(cfg.max_node_id(), 63);
         assert_eq!(cfg.max_sequence_id(), 65535);
 
-        let g = SnowID::with_config(63, cfg).unwrap();
+        let g = SnowID::with_config(63, cfg.clone()).unwrap();
         let id = g.generate();
         let (ts, node, seq) = g.extract.decompose(id);
 
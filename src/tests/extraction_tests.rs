@@ -79,4 +79,85 @@ fn test_epoch_handling() {
         assert!(unix_ts > custom_epoch);
         assert!(unix_ts < (custom_epoch + (1u64 << 41))); // Should be within ~69 years of epoch
     }
+
+    #[test]
+    fn test_explain() {
+        let cfg = SnowIDConfig::builder().epoch(1704067200000).build();
+        let generator = SnowID::with_config(42, cfg.clone()).unwrap();
+
+        // 2024-02-29T12:34:56.789Z, node 42, sequence 7
+        let ts = 1709210096789 - cfg.epoch();
+        let id = generator.create_snowid_with_node(ts, 42, 7);
+        let info = generator.extract.explain(id);
+
+        assert_eq!(info.raw, id);
+        assert_eq!(info.hex, format!("0x{id:016x}"));
+        assert_eq!(info.base62, base62_encode(id));
+        assert_eq!(info.timestamp, ts);
+        assert_eq!(info.unix_ms, 1709210096789);
+        assert_eq!(info.datetime, "2024-02-29T12:34:56.789Z");
+        assert_eq!(info.node, 42);
+        assert_eq!(info.sequence, 7);
+
+        let fields: Vec<&str> = info.binary.split(' ').collect();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].len(), 42);
+        assert_eq!(fields[1], "0000101010");
+        assert_eq!(fields[2], "000000000111");
+        assert_eq!(u64::from_str_radix(&fields.concat(), 2).unwrap(), id);
+    }
+
+    #[test]
+    fn test_explain_display() {
+        let generator = SnowID::new(3).unwrap();
+        let id = generator.generate();
+        let text = generator.extract.explain(id).to_string();
+
+        assert!(text.starts_with(&format!("SnowID {id}\n")));
+        assert_eq!(text.lines().count(), 8);
+        assert!(text.contains("  node:      3"));
+        assert!(text.contains(&format!("  base62:    {}", base62_encode(id))));
+    }
+
+    #[test]
+    fn test_explain_js_safe_tick() {
+        let cfg = SnowIDConfig::builder().js_safe(1000, 4, 6).unwrap().build();
+        let generator = SnowID::with_config(1, cfg.clone()).unwrap();
+        let id = generator.create_snowid_with_node(10, 1, 0);
+        let info = generator.extract.explain(id);
+
+        assert_eq!(info.unix_ms, cfg.epoch() + 10_000);
+        assert_eq!(info.datetime, "2024-01-01T00:00:10.000Z");
+        assert_eq!(info.binary.split(' ').map(str::len).sum::<usize>(), 53);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_explain_json() {
+        let generator = SnowID::new(5).unwrap();
+        let id = generator.generate();
+        let info = generator.extract.explain(id);
+        let json: serde_json::Value = serde_json::to_value(&info).unwrap();
+
+        assert_eq!(json["raw"], id);
+        assert_eq!(json["node"], 5);
+        assert_eq!(json["datetime"], info.datetime.as_str());
+        assert_eq!(json["hex"], info.hex.as_str());
+    }
+
+    #[test]
+    fn test_standalone_extractor() {
+        let cfg = SnowIDConfig::builder().node_bits(12).unwrap().build();
+        let generator = SnowID::with_config(777, cfg.clone()).unwrap();
+        let id = generator.generate();
+
+        // Consumer side: only the config is needed, no generator or node ID
+        let extractor = SnowIDExtractor::new(cfg.clone());
+        assert_eq!(extractor.decompose(id), generator.extract.decompose(id));
+        assert_eq!(extractor.node(id), 777);
+
+        let from_config = cfg.extractor();
+        assert_eq!(from_config.decompose(id), extractor.decompose(id));
+        assert_eq!(from_config.config().node_bits(), 12);
+    }
 }
@@ -0,0 +1,170 @@
+//! JavaScript-safe 53-bit layout tests
+
+#[cfg(test)]
+mod tests {
+    use crate::config::SnowIDConfigError;
+    use crate::tests::test_utils::{assert_unique_and_monotonic, wall_clock_ms};
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_js_safe_layout() {
+        let cfg = SnowIDConfig::builder().js_safe(10, 5, 8).unwrap().build();
+
+        assert_eq!(cfg.timestamp_bits(), 40);
+        assert_eq!(cfg.node_bits(), 5);
+        assert_eq!(cfg.sequence_bits(), 8);
+        assert_eq!(cfg.tick_ms(), 10);
+        assert_eq!(cfg.max_node_id(), 31);
+        assert_eq!(cfg.max_sequence_id(), 255);
+        assert_eq!(cfg.max_id(), SnowIDConfig::MAX_SAFE_INTEGER);
+        assert!(cfg.is_js_safe());
+    }
+
+    #[test]
+    fn test_default_layout_is_not_js_safe() {
+        let cfg = SnowIDConfig::default();
+        assert_eq!(cfg.timestamp_bits(), 42);
+        assert_eq!(cfg.tick_ms(), 1);
+        assert_eq!(cfg.max_id(), u64::MAX);
+        assert!(!cfg.is_js_safe());
+    }
+
+    #[test]
+    fn test_js_safe_lifetime() {
+        // 1 s tick with 43 timestamp bits lasts ~278,000 years
+        let seconds = SnowIDConfig::builder().js_safe(1000, 4, 6).unwrap().build();
+        assert_eq!(seconds.lifetime(), Duration::from_secs(1 << 43));
+
+        // 1 ms tick with 31 timestamp bits lasts ~24.8 days
+        let short = SnowIDConfig::builder().js_safe(1, 10, 12).unwrap().build();
+        assert_eq!(short.lifetime(), Duration::from_millis(1 << 31));
+    }
+
+    #[test]
+    fn test_js_safe_invalid_layout() {
+        let err = SnowIDConfig::builder().js_safe(0, 5, 8).unwrap_err();
+        assert_eq!(err, SnowIDConfigError::TickTooSmall { tick_ms: 0 });
+
+        // 31 timestamp bits: ticks beyond 2^32 ms would overflow `timestamp_ms`
+        let err = SnowIDConfig::builder()
+            .js_safe(u64::MAX, 10, 12)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SnowIDConfigError::TickTooLarge {
+                tick_ms: u64::MAX,
+                max: 1 << 32
+            }
+        );
+        let cfg = SnowIDConfig::builder()
+            .js_safe(1 << 32, 10, 12)
+            .unwrap()
+            .build();
+        assert_eq!(
+            cfg.extractor().timestamp_ms(cfg.max_id()),
+            cfg.max_timestamp() * (1 << 32) + cfg.epoch()
+        );
+
+        let err = SnowIDConfig::builder().js_safe(10, 0, 8).unwrap_err();
+        assert_eq!(
+            err,
+            SnowIDConfigError::UnsupportedJsSafeLayout {
+                node_bits: 0,
+                sequence_bits: 8
+            }
+        );
+
+        let err = SnowIDConfig::builder().js_safe(10, 12, 12).unwrap_err();
+        assert_eq!(
+            err,
+            SnowIDConfigError::UnsupportedJsSafeLayout {
+                node_bits: 12,
+                sequence_bits: 12
+            }
+        );
+    }
+
+    #[test]
+    fn test_node_bits_overrides_js_safe() {
+        let cfg = SnowIDConfig::builder()
+            .js_safe(10, 5, 8)
+            .unwrap()
+            .node_bits(10)
+            .unwrap()
+            .build();
+        assert_eq!(cfg.timestamp_bits(), 42);
+        assert_eq!(cfg.sequence_bits(), 12);
+        assert_eq!(cfg.tick_ms(), 1);
+    }
+
+    #[test]
+    fn test_js_safe_generation() {
+        let cfg = SnowIDConfig::builder().js_safe(10, 5, 8).unwrap().build();
+        let generator = SnowID::with_config(31, cfg).unwrap();
+
+        let ids: Vec<u64> = (0..2000).map(|_| generator.generate()).collect();
+        for &id in &ids {
+            assert!(id <= SnowIDConfig::MAX_SAFE_INTEGER);
+            assert_eq!(generator.extract.node(id), 31);
+            assert!(generator.extract.sequence(id) <= 255);
+        }
+        assert_unique_and_monotonic(ids, 2000);
+    }
+
+    #[test]
+    fn test_js_safe_timestamp_ms() {
+        let cfg = SnowIDConfig::builder().js_safe(10, 5, 8).unwrap().build();
+        let generator = SnowID::with_config(1, cfg.clone()).unwrap();
+
+        let id = generator.generate();
+        let ticks = generator.extract.timestamp(id);
+        let unix_ms = generator.extract.timestamp_ms(id);
+        assert_eq!(unix_ms, ticks * 10 + cfg.epoch());
+
+        let wall = wall_clock_ms(0);
+        assert!(unix_ms <= wall && wall - unix_ms < 1000);
+    }
+
+    #[test]
+    fn test_js_safe_rejects_overflow() {
+        // 31 timestamp bits at 1 ms last ~24.8 days; an epoch 30 days ago is already exhausted
+        let epoch = wall_clock_ms(0) - 30 * 24 * 60 * 60 * 1000;
+        let cfg = SnowIDConfig::builder()
+            .js_safe(1, 10, 12)
+            .unwrap()
+            .epoch(epoch)
+            .build();
+        let generator = SnowID::with_config(1, cfg.clone()).unwrap();
+
+        match generator.try_generate() {
+            Err(SnowIDError::TimestampOverflow { timestamp, max }) => {
+                assert_eq!(max, cfg.max_timestamp());
+                assert!(timestamp > max);
+            }
+            other => panic!("expected TimestampOverflow, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_only_short_layouts_check_overflow() {
+        assert!(!SnowID::new(1).unwrap().can_overflow);
+        let cfg = SnowIDConfig::builder().node_bits(16).unwrap().build();
+        assert!(!SnowID::with_config(1, cfg).unwrap().can_overflow);
+        let cfg = SnowIDConfig::builder().js_safe(1, 10, 12).unwrap().build();
+        assert!(SnowID::with_config(1, cfg).unwrap().can_overflow);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the layout maximum")]
+    fn test_generate_panics_on_overflow() {
+        let epoch = wall_clock_ms(0) - 30 * 24 * 60 * 60 * 1000;
+        let cfg = SnowIDConfig::builder()
+            .js_safe(1, 10, 12)
+            .unwrap()
+            .epoch(epoch)
+            .build();
+        let generator = SnowID::with_config(1, cfg).unwrap();
+        generator.generate();
+    }
+}
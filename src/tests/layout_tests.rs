@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const NEW_EPOCH: u64 = 1577836800000; // 2020-01-01
+
+    fn old_config() -> SnowIDConfig {
+        SnowIDConfig::default()
+    }
+
+    fn new_config() -> SnowIDConfig {
+        SnowIDConfig::builder()
+            .epoch(NEW_EPOCH)
+            .node_bits(8)
+            .unwrap()
+            .build()
+    }
+
+    // 53-bit layouts leave the bits above 52 free for a version tag
+    fn js_old() -> SnowIDConfig {
+        SnowIDConfig::builder()
+            .js_safe(1000, 10, 12)
+            .unwrap()
+            .build()
+    }
+
+    fn js_new() -> SnowIDConfig {
+        SnowIDConfig::builder()
+            .epoch(NEW_EPOCH)
+            .js_safe(1000, 10, 12)
+            .unwrap()
+            .build()
+    }
+
+    fn old_ids() -> Vec<u64> {
+        let generator = SnowID::with_config(1, old_config()).unwrap();
+        let mut ids = Vec::new();
+        for ts in [0, 1, 5_000, 86_400_000] {
+            for node in [0, 1, 255] {
+                for seq in [0, 1, 4095] {
+                    ids.push(generator.create_snowid_with_node(ts, node, seq));
+                }
+            }
+        }
+        ids
+    }
+
+    #[test]
+    fn test_reencode_preserves_order_and_components() {
+        let migration = LayoutMigration::new(old_config(), new_config()).unwrap();
+        let old = old_ids();
+        let new = migration.reencode_all(&old).unwrap();
+
+        assert!(old.windows(2).all(|w| w[0] < w[1]));
+        assert!(new.windows(2).all(|w| w[0] < w[1]));
+
+        let before = old_config().extractor();
+        let after = new_config().extractor();
+        for (&o, &n) in old.iter().zip(&new) {
+            assert_eq!(after.timestamp_ms(n), before.timestamp_ms(o));
+            assert_eq!(after.node(n), before.node(o));
+            assert_eq!(after.sequence(n), before.sequence(o));
+        }
+    }
+
+    #[test]
+    fn test_version_bit_distinguishes_layouts() {
+        let layouts = LayoutSet::new()
+            .with_version_bit("v2", 53, js_new())
+            .unwrap()
+            .with_fallback("v1", js_old())
+            .unwrap();
+        assert_eq!(layouts.names().collect::<Vec<_>>(), ["v2", "v1"]);
+
+        let migration = LayoutMigration::new(js_old(), js_new())
+            .unwrap()
+            .with_version_bit(53)
+            .unwrap();
+        let old = SnowID::with_config(7, js_old()).unwrap().generate();
+        let migrated = migration.reencode(old).unwrap();
+        let fresh = SnowID::with_config(9, js_new()).unwrap().generate() | 1 << 53;
+
+        let decoded_old = layouts.decode(old).unwrap();
+        let decoded_migrated = layouts.decode(migrated).unwrap();
+        assert_eq!(decoded_old.layout, "v1");
+        assert_eq!(decoded_migrated.layout, "v2");
+        assert_eq!(decoded_migrated.unix_ms, decoded_old.unix_ms);
+        assert_eq!(decoded_migrated.node, 7);
+        assert_eq!(decoded_migrated.payload, migrated & !(1 << 53));
+
+        let decoded_fresh = layouts.decode(fresh).unwrap();
+        assert_eq!((decoded_fresh.layout, decoded_fresh.node), ("v2", 9));
+        assert!(fresh >= migrated);
+        assert_eq!(layouts.layout_of(fresh), Some("v2"));
+
+        // Tagged, but with bits above the layout set
+        assert_eq!(
+            layouts.decode(1 << 53 | 1 << 60),
+            Err(LayoutError::NotInLayout {
+                id: 1 << 53 | 1 << 60,
+                layout: "v2"
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_distinguishes_layouts() {
+        let cutover = SnowID::new(1).unwrap().generate();
+        let layouts = LayoutSet::new()
+            .with_range("legacy", 0..=cutover, old_config())
+            .unwrap()
+            .with_range("current", cutover + 1..=u64::MAX, new_config())
+            .unwrap();
+
+        assert_eq!(layouts.layout_of(cutover), Some("legacy"));
+        assert_eq!(layouts.decode(cutover).unwrap().node, 1);
+        assert_eq!(layouts.layout_of(cutover + 1), Some("current"));
+        assert_eq!(layouts.decode(u64::MAX).unwrap().layout, "current");
+        assert_eq!(
+            LayoutSet::new()
+                .with_range("only", 10..=20, old_config())
+                .unwrap()
+                .decode(5),
+            Err(LayoutError::NoMatchingLayout { id: 5 })
+        );
+    }
+
+    #[test]
+    fn test_js_safe_to_default_migration() {
+        let js = SnowIDConfig::builder().js_safe(1000, 4, 6).unwrap().build();
+        let migration = LayoutMigration::new(js.clone(), old_config()).unwrap();
+        let generator = SnowID::with_config(3, js).unwrap();
+        let old: Vec<u64> = (0..5)
+            .map(|ts| generator.create_snowid_with_node(ts, 3, 63))
+            .collect();
+        let new = migration.reencode_all(&old).unwrap();
+        assert!(new.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(old_config().extractor().timestamp(new[1]), 1000);
+    }
+
+    #[test]
+    fn test_migration_errors() {
+        let coarse = SnowIDConfig::builder().js_safe(10, 4, 6).unwrap().build();
+        assert_eq!(
+            LayoutMigration::new(old_config(), coarse).unwrap_err(),
+            LayoutError::IncompatibleTicks {
+                from_tick_ms: 1,
+                to_tick_ms: 10,
+                epoch_gap: 0
+            }
+        );
+
+        let generator = SnowID::with_config(1000, old_config()).unwrap();
+        let id = generator.create_snowid_with_node(0, 1000, 0);
+        let later = SnowIDConfig::builder()
+            .epoch(old_config().epoch() + 1)
+            .build();
+        assert!(matches!(
+            LayoutMigration::new(old_config(), later)
+                .unwrap()
+                .reencode(id),
+            Err(LayoutError::BeforeEpoch { .. })
+        ));
+
+        let narrow = SnowIDConfig::builder().node_bits(8).unwrap().build();
+        assert_eq!(
+            LayoutMigration::new(old_config(), narrow)
+                .unwrap()
+                .reencode(id),
+            Err(LayoutError::DoesNotFit {
+                id,
+                field: "node",
+                value: 1000,
+                max: 255
+            })
+        );
+        assert!(matches!(
+            LayoutMigration::new(js_old(), js_new())
+                .unwrap()
+                .reencode(u64::MAX),
+            Err(LayoutError::NotInSourceLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_layout_sets() {
+        assert_eq!(
+            LayoutSet::new()
+                .with_version_bit("v2", 52, js_new())
+                .unwrap_err(),
+            LayoutError::InvalidVersionBit { bit: 52, min: 53 }
+        );
+        // The standard layout uses all 64 bits
+        assert_eq!(
+            LayoutSet::new()
+                .with_version_bit("v2", 63, new_config())
+                .unwrap_err(),
+            LayoutError::InvalidVersionBit { bit: 63, min: 64 }
+        );
+        assert_eq!(
+            LayoutSet::new()
+                .with_fallback("v1", old_config())
+                .unwrap()
+                .with_version_bit("v2", 53, js_new())
+                .unwrap_err(),
+            LayoutError::Unreachable {
+                name: "v2",
+                shadowed_by: "v1"
+            }
+        );
+        assert_eq!(
+            LayoutSet::new()
+                .with_range("a", 0..=10, old_config())
+                .unwrap()
+                .with_range("b", 10..=20, old_config())
+                .unwrap_err(),
+            LayoutError::OverlappingRange {
+                name: "b",
+                other: "a"
+            }
+        );
+        assert_eq!(
+            LayoutSet::new()
+                .with_range("a", 0..=10, old_config())
+                .unwrap()
+                .with_range("b", 2..=8, old_config())
+                .unwrap_err(),
+            LayoutError::Unreachable {
+                name: "b",
+                shadowed_by: "a"
+            }
+        );
+        assert_eq!(
+            LayoutSet::new()
+                .with_version_bit("v", 53, js_old())
+                .unwrap()
+                .with_fallback("v", old_config())
+                .unwrap_err(),
+            LayoutError::DuplicateLayout { name: "v" }
+        );
+    }
+
+    #[test]
+    fn test_version_bits_checked_against_ranges() {
+        let tagged = 1u64 << 53;
+
+        // A range reaching into IDs with the bit set overlaps the version-bit layout
+        assert_eq!(
+            LayoutSet::new()
+                .with_range("old", 0..=tagged, js_old())
+                .unwrap()
+                .with_version_bit("new", 53, js_new())
+                .unwrap_err(),
+            LayoutError::OverlappingRange {
+                name: "new",
+                other: "old"
+            }
+        );
+        assert_eq!(
+            LayoutSet::new()
+                .with_version_bit("new", 53, js_new())
+                .unwrap()
+                .with_range("old", tagged - 10..=tagged + 10, js_old())
+                .unwrap_err(),
+            LayoutError::OverlappingRange {
+                name: "old",
+                other: "new"
+            }
+        );
+        // Every ID of the range carries the bit, so the range can never match
+        assert_eq!(
+            LayoutSet::new()
+                .with_version_bit("new", 53, js_new())
+                .unwrap()
+                .with_range("old", tagged..=tagged + 10, js_old())
+                .unwrap_err(),
+            LayoutError::Unreachable {
+                name: "old",
+                shadowed_by: "new"
+            }
+        );
+        // Ranges below the bit and higher version bits coexist
+        let set = LayoutSet::new()
+            .with_range("old", 0..=tagged - 1, js_old())
+            .unwrap()
+            .with_version_bit("new", 53, js_new())
+            .unwrap()
+            .with_version_bit("newer", 54, js_new())
+            .unwrap();
+        assert_eq!(set.layout_of(5), Some("old"));
+        assert_eq!(set.layout_of(tagged | 5), Some("new"));
+        assert_eq!(set.layout_of((1u64 << 54) | 5), Some("newer"));
+    }
+}
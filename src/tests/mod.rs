@@ -1,11 +1,26 @@
 mod base62_tests;
 mod boundary_tests;
+mod claim_tests;
 mod concurrent_tests;
 mod config_tests;
 mod core_tests;
 mod edge_case_tests;
 mod extraction_tests;
+mod js_safe_tests;
+mod layout_tests;
+mod pool_tests;
+mod prefix_tests;
+mod registry_tests;
+mod role_tests;
+// Lints from newer clippy releases on tests that predate them
+#[allow(clippy::collapsible_if, clippy::len_zero)]
 mod sequence_tests;
+#[cfg(feature = "mmap")]
+mod shared_tests;
+mod snapshot_tests;
+#[allow(clippy::manual_abs_diff)]
 pub mod test_utils;
+#[allow(clippy::manual_range_contains)]
 mod timestamp_tests;
 mod timing_tests;
+mod validation_tests;
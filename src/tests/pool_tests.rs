@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+
+    use crate::tests::test_utils::assert_unique_ids;
+    use crate::*;
+
+    #[test]
+    fn test_round_robin_cycles_nodes() {
+        let pool = SnowIDPool::new([4, 5, 6]).unwrap();
+        let nodes: Vec<u16> = (0..6)
+            .map(|_| pool.extractor().node(pool.generate()))
+            .collect();
+        assert_eq!(nodes, [4, 5, 6, 4, 5, 6]);
+        assert_eq!(pool.issued(), [(4, 2), (5, 2), (6, 2)]);
+    }
+
+    #[test]
+    fn test_least_used_balances_nodes() {
+        let pool =
+            SnowIDPool::with_config([1, 2], SnowIDConfig::default(), PoolStrategy::LeastUsed)
+                .unwrap();
+        for _ in 0..100 {
+            pool.generate();
+        }
+        assert_eq!(pool.issued(), [(1, 50), (2, 50)]);
+    }
+
+    #[test]
+    fn test_exhausted_node_moves_on_without_waiting() {
+        let config = SnowIDConfig::builder().node_bits(16).unwrap().build();
+        let pool =
+            SnowIDPool::with_config([0, 1], config.clone(), PoolStrategy::RoundRobin).unwrap();
+
+        // Exhaust node 0 for a tick well ahead of the clock, so waiting would take a second
+        let generator = &pool.generators[0];
+        let ahead = generator.now_ms() + 1000;
+        generator.state.store(
+            (ahead << 16) | config.max_sequence_id() as u64,
+            Ordering::Release,
+        );
+
+        let start = std::time::Instant::now();
+        let id = pool.generate();
+        assert_eq!(pool.extractor().node(id), 1);
+        assert!(start.elapsed() < std::time::Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_pool_exceeds_single_node_rate() {
+        // 6 sequence bits: a single node issues at most 64 IDs per millisecond
+        let config = SnowIDConfig::builder().node_bits(16).unwrap().build();
+        let pool = SnowIDPool::with_config(0..8, config, PoolStrategy::RoundRobin).unwrap();
+        let ids: Vec<u64> = (0..512).map(|_| pool.generate()).collect();
+        assert_unique_ids(&ids, 512);
+
+        let nodes: HashSet<u16> = ids.iter().map(|&id| pool.extractor().node(id)).collect();
+        assert_eq!(nodes.len(), 8);
+    }
+
+    #[test]
+    fn test_pool_concurrent_unique() {
+        let pool = Arc::new(SnowIDPool::new(0..4).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = pool.clone();
+                thread::spawn(move || (0..5000).map(|_| pool.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+        let ids: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        assert_unique_ids(&ids, 20_000);
+        let total: u64 = pool.issued().iter().map(|(_, n)| n).sum();
+        assert_eq!(total, 20_000);
+    }
+
+    #[test]
+    fn test_pool_errors() {
+        assert_eq!(
+            SnowIDPool::new(std::iter::empty()).unwrap_err(),
+            PoolError::Empty
+        );
+        assert_eq!(
+            SnowIDPool::new([1, 2, 1]).unwrap_err(),
+            PoolError::DuplicateNode { node_id: 1 }
+        );
+        assert!(matches!(
+            SnowIDPool::new([1024]),
+            Err(PoolError::Node(SnowIDError::InvalidNodeId {
+                node_id: 1024,
+                ..
+            }))
+        ));
+
+        let pool = SnowIDPool::new([9]).unwrap();
+        assert_eq!(pool.len(), 1);
+        assert!(!pool.is_empty());
+        assert_eq!(pool.node_ids().collect::<Vec<_>>(), [9]);
+        assert_eq!(pool.strategy(), PoolStrategy::RoundRobin);
+    }
+}
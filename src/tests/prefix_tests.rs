@@ -0,0 +1,100 @@
+//! Prefixed ID tests
+
+#[cfg(test)]
+mod tests {
+    use crate::base62::DecodeError;
+    use crate::prefix::{Prefix, PrefixError, PrefixRegistry};
+    use crate::*;
+
+    #[test]
+    fn test_prefixed_generation() {
+        let generator = SnowID::new(7).unwrap();
+        let users = generator.prefixed("usr").unwrap();
+
+        let (encoded, raw) = users.generate_with_raw();
+        assert_eq!(encoded, format!("usr_{}", base62_encode(raw)));
+        assert_eq!(users.decode(&encoded).unwrap(), raw);
+
+        let (_, node, _) = users.decompose(&encoded).unwrap();
+        assert_eq!(node, 7);
+    }
+
+    #[test]
+    fn test_prefixed_generate_into() {
+        let generator = SnowID::new(1).unwrap();
+        let orders = generator.prefixed("order_item").unwrap();
+        let mut buf = [0u8; prefix::MAX_LEN];
+
+        let (encoded, raw) = orders.generate_into(&mut buf);
+        assert!(encoded.starts_with("order_item_"));
+        assert_eq!(orders.decode(encoded).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_invalid_prefixes() {
+        for invalid in ["", "Usr", "1usr", "usr_", "us-r", "abcdefghijklmnopq"] {
+            assert!(
+                matches!(Prefix::new(invalid), Err(PrefixError::InvalidPrefix { .. })),
+                "{invalid:?} should be rejected"
+            );
+        }
+        assert!(SnowID::new(1).unwrap().prefixed("USR").is_err());
+    }
+
+    #[test]
+    fn test_wrong_prefix_rejected() {
+        let usr = Prefix::new("usr").unwrap();
+        let org = Prefix::new("org").unwrap();
+        let encoded = org.encode(12345);
+
+        assert!(matches!(
+            usr.decode(&encoded),
+            Err(PrefixError::UnexpectedPrefix { ref expected, ref found })
+                if expected == "usr" && found == "org"
+        ));
+        assert!(matches!(
+            usr.decode(&base62_encode(12345)),
+            Err(PrefixError::UnexpectedPrefix { ref found, .. }) if found.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        let usr = Prefix::new("usr").unwrap();
+        let err = usr.decode("usr_abc!").unwrap_err();
+        assert!(matches!(
+            err,
+            PrefixError::Decode(DecodeError::InvalidCharacter {
+                byte: b'!',
+                position: 3
+            })
+        ));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = PrefixRegistry::new();
+        let usr = registry.register("usr").unwrap();
+        registry.register("org").unwrap();
+
+        assert!(matches!(
+            registry.register("usr"),
+            Err(PrefixError::DuplicatePrefix { ref prefix }) if prefix == "usr"
+        ));
+        assert!(matches!(
+            registry.register("bad prefix"),
+            Err(PrefixError::InvalidPrefix { .. })
+        ));
+
+        let (prefix, id) = registry.parse(&usr.encode(42)).unwrap();
+        assert_eq!(prefix, &usr);
+        assert_eq!(id, 42);
+        assert_eq!(registry.get("org").unwrap().as_str(), "org");
+
+        assert!(matches!(
+            registry.parse("txn_1"),
+            Err(PrefixError::UnknownPrefix { ref prefix }) if prefix == "txn"
+        ));
+    }
+}
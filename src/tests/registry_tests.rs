@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    // The registry is process-wide, so every test uses its own epoch
+    fn config(epoch: u64) -> SnowIDConfig {
+        SnowIDConfig::builder().epoch(epoch).build()
+    }
+
+    #[test]
+    fn test_register_rejects_live_duplicate() {
+        let config = config(1_600_000_000_001);
+        let first = SnowID::register(1, config.clone()).unwrap();
+        assert_eq!(
+            SnowID::register(1, config.clone()).unwrap_err(),
+            SnowIDError::DuplicateGenerator { node_id: 1 }
+        );
+
+        // Other nodes and layouts are independent
+        assert!(SnowID::register(2, config.clone()).is_ok());
+        assert!(SnowID::register(1, self::config(1_600_000_000_002)).is_ok());
+
+        // Dropping the live generator frees the slot
+        drop(first);
+        assert!(SnowID::register(1, config).is_ok());
+    }
+
+    #[test]
+    fn test_shared_returns_live_instance() {
+        let config = config(1_600_000_000_003);
+        let a = SnowID::shared(3, config.clone()).unwrap();
+        let b = SnowID::shared(3, config.clone()).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(matches!(
+            SnowID::register(3, config.clone()),
+            Err(SnowIDError::DuplicateGenerator { node_id: 3 })
+        ));
+
+        // A shared handle never repeats IDs across callers
+        let ids = [a.generate(), b.generate(), a.generate()];
+        assert!(ids[0] < ids[1] && ids[1] < ids[2]);
+
+        drop((a, b));
+        let c = SnowID::shared(3, config).unwrap();
+        assert_eq!(Arc::strong_count(&c), 1);
+    }
+
+    #[test]
+    fn test_registry_validates_node_id() {
+        let config = config(1_600_000_000_004);
+        assert!(matches!(
+            SnowID::register(1024, config.clone()),
+            Err(SnowIDError::InvalidNodeId { .. })
+        ));
+        assert!(matches!(
+            SnowID::shared(1024, config),
+            Err(SnowIDError::InvalidNodeId { .. })
+        ));
+    }
+
+    #[test]
+    fn test_untracked_generators_are_not_registered() {
+        let config = config(1_600_000_000_005);
+        let _plain = SnowID::with_config(5, config.clone()).unwrap();
+        assert!(SnowID::register(5, config).is_ok());
+    }
+
+    #[test]
+    fn test_replacement_resumes_after_dropped_generator() {
+        let config = config(1_600_000_000_006);
+        let first = SnowID::register(6, config.clone()).unwrap();
+        let ids: Vec<u64> = (0..100).map(|_| first.generate()).collect();
+        let last = ids[99];
+        let state = first.state.load(std::sync::atomic::Ordering::Acquire);
+        drop(first);
+
+        // Same tick, same node: continues after the dropped generator's state
+        let second = SnowID::register(6, config.clone()).unwrap();
+        assert_eq!(
+            second.state.load(std::sync::atomic::Ordering::Acquire),
+            state
+        );
+        assert!(second.generate() > last);
+
+        drop(second);
+        let third = SnowID::shared(6, config).unwrap();
+        assert!(third.generate() > last);
+    }
+}
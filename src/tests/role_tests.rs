@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn roles_config() -> SnowIDConfig {
+        SnowIDConfig::builder()
+            .node_role("services", 0..=899)
+            .unwrap()
+            .node_role("batch", 900..=999)
+            .unwrap()
+            .node_role("tests", 1000..=1023)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn test_roles_declared_in_order() {
+        let config = roles_config();
+        let names: Vec<&str> = config
+            .roles()
+            .iter()
+            .map(|role| role.name.as_str())
+            .collect();
+        assert_eq!(names, ["services", "batch", "tests"]);
+
+        let batch = config.role("batch").unwrap();
+        assert_eq!(batch.nodes(), 900..=999);
+        assert_eq!(batch.size(), 100);
+        assert_eq!(config.role_of(950), Some(batch));
+        assert_eq!(config.role("unknown"), None);
+        assert!(SnowIDConfig::default().roles().is_empty());
+    }
+
+    #[test]
+    fn test_generator_for_role() {
+        let config = roles_config();
+        let generator = SnowID::for_role("batch", 3, config.clone()).unwrap();
+        assert_eq!(generator.node_id, 903);
+
+        let id = generator.generate();
+        assert_eq!(generator.extract.role(id), Some("batch"));
+        assert_eq!(config.extractor().role(id), Some("batch"));
+        assert_eq!(generator.extract.explain(id).role.as_deref(), Some("batch"));
+        assert!(
+            generator
+                .extract
+                .explain(id)
+                .to_string()
+                .contains("  node:      903 (batch)")
+        );
+
+        let tests = SnowID::for_role("tests", 23, config).unwrap();
+        assert_eq!(tests.extract.role(tests.generate()), Some("tests"));
+    }
+
+    #[test]
+    fn test_for_role_errors() {
+        let config = roles_config();
+        assert_eq!(
+            SnowID::for_role("batch", 100, config.clone()).unwrap_err(),
+            SnowIDError::RoleIndexOutOfRange {
+                role: "batch".to_owned(),
+                index: 100,
+                size: 100
+            }
+        );
+        assert_eq!(
+            SnowID::for_role("missing", 0, config).unwrap_err(),
+            SnowIDError::UnknownRole {
+                role: "missing".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_nodes_outside_roles_rejected() {
+        let config = SnowIDConfig::builder()
+            .node_role("services", 0..=99)
+            .unwrap()
+            .build();
+        assert!(SnowID::with_config(99, config.clone()).is_ok());
+        assert_eq!(
+            SnowID::with_config(100, config).unwrap_err(),
+            SnowIDError::NodeOutsideRoles { node_id: 100 }
+        );
+        // Without roles every node ID within the layout stays valid
+        assert!(SnowID::new(100).is_ok());
+        assert_eq!(SnowIDConfig::default().extractor().role(0), None);
+    }
+
+    #[test]
+    fn test_invalid_role_declarations() {
+        let builder = || SnowIDConfig::builder().node_role("a", 0..=9).unwrap();
+
+        assert_eq!(
+            builder().node_role("a", 10..=19).unwrap_err(),
+            SnowIDConfigError::DuplicateRole {
+                name: "a".to_owned()
+            }
+        );
+        assert_eq!(
+            builder().node_role("b", 5..=19).unwrap_err(),
+            SnowIDConfigError::OverlappingRoles {
+                name: "b".to_owned(),
+                other: "a".to_owned()
+            }
+        );
+        let (first, last) = (20, 10);
+        assert!(matches!(
+            builder().node_role("b", first..=last),
+            Err(SnowIDConfigError::InvalidRoleRange { name, .. }) if name == "b"
+        ));
+        assert_eq!(
+            builder().node_role("b", 10..=1024).unwrap_err(),
+            SnowIDConfigError::InvalidRoleRange {
+                name: "b".to_owned(),
+                first: 10,
+                last: 1024,
+                max: 1023
+            }
+        );
+    }
+
+    #[test]
+    fn test_role_names_from_runtime_config() {
+        // e.g. parsed from a config file: names are owned and any number of roles fits
+        let mut builder = SnowIDConfig::builder();
+        for i in 0..32u16 {
+            builder = builder
+                .node_role(format!("shard-{}", i), i * 32..=i * 32 + 31)
+                .unwrap();
+        }
+        let config = builder.build();
+        assert_eq!(config.roles().len(), 32);
+
+        let generator = SnowID::for_role("shard-20", 1, config).unwrap();
+        assert_eq!(generator.node_id, 641);
+        assert_eq!(
+            generator.extract.role(generator.generate()),
+            Some("shard-20")
+        );
+    }
+
+    #[test]
+    fn test_cloned_configs_share_the_role_table() {
+        let a = roles_config();
+        let b = a.clone();
+        assert!(std::ptr::eq(a.roles(), b.roles()));
+        // Roles live outside the layout, behind one pointer
+        assert!(std::mem::size_of::<SnowIDConfig>() <= 80);
+
+        // Separately built tables are owned by their configs, not a process-wide cache
+        let c = roles_config();
+        assert!(!std::ptr::eq(a.roles(), c.roles()));
+        assert_eq!(a.roles(), c.roles());
+        drop(a);
+        assert_eq!(b.roles().len(), c.roles().len());
+    }
+
+    #[test]
+    fn test_roles_rechecked_when_node_bits_shrink() {
+        let result = SnowIDConfig::builder()
+            .node_role("wide", 0..=1023)
+            .unwrap()
+            .node_bits(8);
+        assert!(matches!(
+            result,
+            Err(SnowIDConfigError::InvalidRoleRange { name, max: 255, .. }) if name == "wide"
+        ));
+
+        let config = SnowIDConfig::builder()
+            .node_role("low", 0..=15)
+            .unwrap()
+            .js_safe(1000, 4, 6)
+            .unwrap()
+            .build();
+        assert_eq!(config.role("low").unwrap().nodes(), 0..=15);
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::tests::test_utils::{assert_unique_ids, temp_dir};
+    use crate::*;
+
+    #[test]
+    fn test_shared_state_persists_across_instances() {
+        let path = temp_dir("shared-persist").join("node-1.state");
+        let first = SharedSnowID::open(&path, 1).unwrap();
+        let a = first.generate();
+        drop(first);
+
+        // A later "process" continues after the stored state
+        let second = SharedSnowID::open(&path, 1).unwrap();
+        let b = second.generate();
+        assert!(b > a);
+        assert_eq!(second.extractor().node(b), 1);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 72);
+    }
+
+    #[test]
+    fn test_instances_never_overlap() {
+        // Separate handles lock like separate processes
+        let path = temp_dir("shared-concurrent").join("node-3.state");
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = SharedSnowID::open(&path, 3).unwrap();
+                thread::spawn(move || (0..500).map(|_| shared.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+        let ids: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        assert_unique_ids(&ids, 2000);
+    }
+
+    #[test]
+    fn test_threads_sharing_one_instance() {
+        let path = temp_dir("shared-threads").join("node-4.state");
+        let shared = Arc::new(SharedSnowID::open(&path, 4).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || (0..500).map(|_| shared.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+        let ids: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        assert_unique_ids(&ids, 2000);
+    }
+
+    #[test]
+    fn test_open_instances_see_each_others_ids() {
+        let path = temp_dir("shared-mapped").join("node-5.state");
+        let first = SharedSnowID::open(&path, 5).unwrap();
+        let second = SharedSnowID::open(&path, 5).unwrap();
+
+        // Both update the same mapped state word, without reopening the file
+        let a = first.generate();
+        let b = second.generate();
+        let c = first.generate();
+        assert!(a < b && b < c);
+
+        let bytes = std::fs::read(&path).unwrap();
+        let state = u64::from_ne_bytes(bytes[64..72].try_into().unwrap());
+        let extractor = first.extractor();
+        assert_eq!(state >> 16, extractor.timestamp(c));
+        assert_eq!(state & 0xFFFF, extractor.sequence(c) as u64);
+    }
+
+    #[test]
+    fn test_rejects_foreign_state_file() {
+        let path = temp_dir("shared-foreign").join("state");
+        SharedSnowID::open(&path, 1).unwrap().generate();
+
+        assert_eq!(
+            SharedSnowID::open(&path, 2).unwrap_err(),
+            SharedError::NodeMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+        let other = SnowIDConfig::builder().node_bits(8).unwrap().build();
+        assert_eq!(
+            SharedSnowID::open_with_config(&path, 1, other).unwrap_err(),
+            SharedError::Snapshot(SnapshotError::LayoutMismatch)
+        );
+
+        std::fs::write(&path, b"garbage").unwrap();
+        assert!(matches!(
+            SharedSnowID::open(&path, 1),
+            Err(SharedError::Io { reason, .. }) if reason == "not a shared SnowID state file"
+        ));
+        std::fs::write(&path, [0u8; 72]).unwrap();
+        assert!(matches!(
+            SharedSnowID::open(&path, 1),
+            Err(SharedError::Io { reason, .. }) if reason == "not a shared SnowID state file"
+        ));
+    }
+}
@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::*;
+
+    #[test]
+    fn test_snapshot_captures_last_issued() {
+        let generator = SnowID::new(7).unwrap();
+        let id = generator.generate();
+        let snapshot = generator.snapshot();
+        let (timestamp, node, sequence) = generator.extract.decompose(id);
+
+        assert_eq!(snapshot.node_id, node);
+        assert_eq!(snapshot.timestamp, timestamp);
+        assert_eq!(snapshot.sequence, sequence);
+        assert!(snapshot.matches_layout(&generator.config));
+    }
+
+    #[test]
+    fn test_restore_continues_strictly_after() {
+        let predecessor = SnowID::new(3).unwrap();
+        let last = (0..100).map(|_| predecessor.generate()).last().unwrap();
+
+        let successor = SnowID::restore(predecessor.snapshot()).unwrap();
+        assert_eq!(successor.node_id, 3);
+        let ids: Vec<u64> = (0..100).map(|_| successor.generate()).collect();
+        assert!(ids[0] > last);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_restore_ahead_of_clock() {
+        // Snapshot from a predecessor whose clock ran ahead: the successor keeps counting
+        // within the snapshot's tick instead of reissuing earlier IDs
+        let generator = SnowID::new(1).unwrap();
+        let mut snapshot = generator.snapshot();
+        snapshot.timestamp = generator.extract.timestamp(generator.generate()) + 5;
+        snapshot.sequence = 10;
+
+        let successor = SnowID::restore(snapshot).unwrap();
+        let id = successor.generate();
+        assert_eq!(successor.extract.decompose(id), (snapshot.timestamp, 1, 11));
+    }
+
+    #[test]
+    fn test_restore_js_safe_layout() {
+        let config = SnowIDConfig::builder()
+            .epoch(1_700_000_000_000)
+            .js_safe(10, 6, 8)
+            .unwrap()
+            .build();
+        let predecessor = SnowID::with_config(9, config).unwrap();
+        let last = predecessor.generate();
+
+        let snapshot = predecessor.snapshot();
+        let successor = SnowID::restore(snapshot).unwrap();
+        assert!(snapshot.matches_layout(&successor.config));
+        assert!(successor.generate() > last);
+    }
+
+    #[test]
+    fn test_restore_js_safe_layouts_with_42_timestamp_bits() {
+        // node + sequence = 11 leaves 42 timestamp bits, like the standard layout
+        for (tick_ms, node_bits, sequence_bits) in [(10, 5, 6), (1, 8, 3)] {
+            let config = SnowIDConfig::builder()
+                .js_safe(tick_ms, node_bits, sequence_bits)
+                .unwrap()
+                .build();
+            assert_eq!(config.timestamp_bits(), 42);
+            let predecessor = SnowID::with_config(3, config).unwrap();
+            let last = predecessor.generate();
+
+            let snapshot = SnowIDSnapshot::from_bytes(&predecessor.snapshot().to_bytes()).unwrap();
+            assert_eq!(snapshot.config().unwrap().tick_ms(), tick_ms);
+            let successor = SnowID::restore(snapshot).unwrap();
+            assert!(snapshot.matches_layout(&successor.config));
+            assert_eq!(successor.node_id, 3);
+            assert!(successor.generate() > last);
+        }
+    }
+
+    #[test]
+    fn test_restore_checks_node_roles() {
+        let config = SnowIDConfig::builder()
+            .node_role("services", 0..=9)
+            .unwrap()
+            .build();
+        let snapshot = SnowID::with_config(5, config.clone()).unwrap().snapshot();
+
+        // Roles are not in the snapshot: only `restore_with_config` keeps them
+        assert!(SnowID::restore(snapshot).unwrap().config.roles().is_empty());
+        let restored = SnowID::restore_with_config(snapshot, config.clone()).unwrap();
+        assert_eq!(restored.config.roles().len(), 1);
+
+        let foreign = SnowID::new(500).unwrap().snapshot();
+        assert_eq!(
+            SnowID::restore_with_config(foreign, config).unwrap_err(),
+            SnapshotError::NodeOutsideRoles { node_id: 500 }
+        );
+    }
+
+    #[test]
+    fn test_restore_with_config_keeps_spin_settings() {
+        let config = SnowIDConfig::builder().enable_spin(false).build();
+        let predecessor = SnowID::with_config(2, config.clone()).unwrap();
+        predecessor.generate();
+
+        let successor = SnowID::restore_with_config(predecessor.snapshot(), config).unwrap();
+        assert!(!successor.config.spin_enabled());
+        assert_eq!(
+            successor.state.load(Ordering::Acquire),
+            predecessor.state.load(Ordering::Acquire)
+        );
+
+        let other = SnowIDConfig::builder().node_bits(8).unwrap().build();
+        assert_eq!(
+            SnowID::restore_with_config(predecessor.snapshot(), other).unwrap_err(),
+            SnapshotError::LayoutMismatch
+        );
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_snapshot() {
+        let mut snapshot = SnowID::new(1).unwrap().snapshot();
+        snapshot.sequence = 4096;
+        assert!(matches!(
+            SnowID::restore(snapshot),
+            Err(SnapshotError::InvalidState { sequence: 4096, .. })
+        ));
+
+        let mut snapshot = SnowID::new(1).unwrap().snapshot();
+        snapshot.node_bits = 3;
+        assert!(matches!(
+            SnowID::restore(snapshot),
+            Err(SnapshotError::InvalidLayout(_))
+        ));
+
+        let mut snapshot = SnowID::new(1).unwrap().snapshot();
+        snapshot.tick_ms = 10;
+        assert_eq!(
+            SnowID::restore(snapshot).unwrap_err(),
+            SnapshotError::LayoutMismatch
+        );
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let generator = SnowID::new(513).unwrap();
+        generator.generate();
+        let snapshot = generator.snapshot();
+
+        let bytes = snapshot.to_bytes();
+        assert_eq!(bytes.len(), SnowIDSnapshot::LEN);
+        assert_eq!(SnowIDSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+
+        assert_eq!(
+            SnowIDSnapshot::from_bytes(&bytes[..31]),
+            Err(SnapshotError::InvalidLength { len: 31 })
+        );
+        let mut future = bytes;
+        future[0] = 2;
+        assert_eq!(
+            SnowIDSnapshot::from_bytes(&future),
+            Err(SnapshotError::UnsupportedVersion { version: 2 })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let generator = SnowID::new(42).unwrap();
+        generator.generate();
+        let snapshot = generator.snapshot();
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("\"node_id\":42"));
+        let parsed: SnowIDSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, snapshot);
+
+        let reader: SnowIDSnapshot = serde_json::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(reader, snapshot);
+
+        assert!(serde_json::from_str::<SnowIDSnapshot>(r#"{"node_id":1}"#).is_err());
+        let too_big = json.replace("\"node_id\":42", "\"node_id\":70000");
+        assert!(serde_json::from_str::<SnowIDSnapshot>(&too_big).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_reads_struct_as_sequence() {
+        let generator = SnowID::new(42).unwrap();
+        generator.generate();
+        let snapshot = generator.snapshot();
+
+        // Compact formats such as bincode and postcard send the fields in order
+        let fields = format!(
+            "[{}, {}, {}, {}, {}, {}, {}, {}]",
+            snapshot.node_id,
+            snapshot.timestamp,
+            snapshot.sequence,
+            snapshot.timestamp_bits,
+            snapshot.node_bits,
+            snapshot.sequence_bits,
+            snapshot.epoch,
+            snapshot.tick_ms
+        );
+        let parsed: SnowIDSnapshot = serde_json::from_str(&fields).unwrap();
+        assert_eq!(parsed, snapshot);
+
+        assert!(serde_json::from_str::<SnowIDSnapshot>("[42, 1, 0]").is_err());
+        assert!(serde_json::from_str::<SnowIDSnapshot>("[70000, 1, 0, 42, 10, 12, 0, 1]").is_err());
+    }
+
+    #[test]
+    fn test_into_snapshot_hands_off() {
+        let old = SnowID::new(7).unwrap();
+        let last = old.generate();
+        let snapshot = old.into_snapshot();
+
+        let new = SnowID::restore(snapshot).unwrap();
+        assert_eq!(snapshot.node_id, 7);
+        assert!(new.generate() > last);
+    }
+}
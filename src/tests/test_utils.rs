@@ -73,3 +73,11 @@ pub fn assert_timestamp_accurate(ts: u64, epoch: u64, tolerance_ms: u64) {
         tolerance_ms
     );
 }
+
+/// Create an empty, test-specific directory under the system temp dir
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("snowid-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+    dir
+}
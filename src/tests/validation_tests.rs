@@ -0,0 +1,118 @@
+//! Validation tests for untrusted incoming IDs
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::time::Duration;
+
+    const NOW: u64 = 1740000000000; // 2025-02-19
+
+    fn id_at(generator: &SnowID, unix_ms: u64, node: u16) -> u64 {
+        generator.create_snowid_with_node(unix_ms - generator.config.epoch(), node, 0)
+    }
+
+    #[test]
+    fn test_valid_id() {
+        let generator = SnowID::new(5).unwrap();
+        let id = generator.generate();
+        let policy = ValidationPolicy::new();
+        assert_eq!(generator.extract.validate(id, &policy), Ok(()));
+    }
+
+    #[test]
+    fn test_timestamp_at_epoch() {
+        let extractor = SnowIDConfig::default().extractor();
+        let policy = ValidationPolicy::default();
+
+        // Small garbage values decode to timestamp 0
+        assert_eq!(
+            extractor.validate_at(12345, &policy, NOW),
+            Err(ValidationError::TimestampAtEpoch)
+        );
+    }
+
+    #[test]
+    fn test_not_before() {
+        let generator = SnowID::new(1).unwrap();
+        let policy = ValidationPolicy::new().not_before(NOW - 1000);
+
+        let old = id_at(&generator, NOW - 1001, 1);
+        assert_eq!(
+            generator.extract.validate_at(old, &policy, NOW),
+            Err(ValidationError::TooOld {
+                unix_ms: NOW - 1001,
+                not_before_ms: NOW - 1000
+            })
+        );
+
+        let ok = id_at(&generator, NOW - 1000, 1);
+        assert_eq!(generator.extract.validate_at(ok, &policy, NOW), Ok(()));
+    }
+
+    #[test]
+    fn test_too_far_in_future() {
+        let generator = SnowID::new(1).unwrap();
+        let policy = ValidationPolicy::new().max_future(Duration::from_secs(5));
+
+        let skewed = id_at(&generator, NOW + 5000, 1);
+        assert_eq!(generator.extract.validate_at(skewed, &policy, NOW), Ok(()));
+
+        let future = id_at(&generator, NOW + 5001, 1);
+        assert_eq!(
+            generator.extract.validate_at(future, &policy, NOW),
+            Err(ValidationError::TooFarInFuture {
+                unix_ms: NOW + 5001,
+                max_unix_ms: NOW + 5000
+            })
+        );
+    }
+
+    #[test]
+    fn test_node_range_and_set() {
+        let generator = SnowID::new(1).unwrap();
+        let in_range = id_at(&generator, NOW, 10);
+        let out_of_range = id_at(&generator, NOW, 11);
+
+        let range = ValidationPolicy::new().node_range(0..=10);
+        assert_eq!(generator.extract.validate_at(in_range, &range, NOW), Ok(()));
+        assert_eq!(
+            generator.extract.validate_at(out_of_range, &range, NOW),
+            Err(ValidationError::NodeNotAllowed { node: 11 })
+        );
+
+        let set = ValidationPolicy::new().allowed_nodes([3, 11]);
+        assert_eq!(
+            generator.extract.validate_at(in_range, &set, NOW),
+            Err(ValidationError::NodeNotAllowed { node: 10 })
+        );
+        assert_eq!(
+            generator.extract.validate_at(out_of_range, &set, NOW),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_reserved_bits() {
+        let cfg = SnowIDConfig::builder().js_safe(10, 5, 8).unwrap().build();
+        let generator = SnowID::with_config(1, cfg).unwrap();
+        let id = generator.generate();
+        let policy = ValidationPolicy::new();
+
+        assert_eq!(generator.extract.validate(id, &policy), Ok(()));
+
+        let forged = id | (1 << 60);
+        assert_eq!(
+            generator.extract.validate(forged, &policy),
+            Err(ValidationError::ReservedBitsSet {
+                id: forged,
+                max_id: SnowIDConfig::MAX_SAFE_INTEGER
+            })
+        );
+    }
+
+    #[test]
+    fn test_validation_error_display() {
+        let err = ValidationError::NodeNotAllowed { node: 7 };
+        assert_eq!(err.to_string(), "Node 7 is not allowed");
+    }
+}
@@ -0,0 +1,291 @@
+//! UUID interop for SnowID values (requires the `uuid` feature)
+//!
+//! - `to_uuid_v8` / `from_uuid_v8`: The SnowID bits in a custom UUIDv8, no configuration needed
+//! - `to_uuid_v7` / `from_uuid_v7`: A UUIDv7-shaped value with the real Unix millisecond
+//!   timestamp in front, so databases and tools that read v7 timestamps see the creation time
+//!
+//! Both mappings are lossless and sort in the same order as the SnowIDs they carry, so
+//! timestamp, node and sequence stay recoverable through `SnowIDExtractor`.
+
+use std::error::Error;
+use std::fmt;
+
+use ::uuid::{Uuid, Variant};
+
+use crate::SnowIDExtractor;
+
+/// Largest Unix millisecond timestamp a UUIDv7 can hold (48 bits)
+const MAX_V7_UNIX_MS: u64 = (1 << 48) - 1;
+
+/// Embed a SnowID in a UUIDv8
+///
+/// The 64 ID bits fill the custom fields in order (48 + 12 + 4 bits), all remaining bits are zero
+pub fn to_uuid_v8(id: u64) -> Uuid {
+    let mut bytes = [0u8; 16];
+    bytes[..6].copy_from_slice(&(id >> 16).to_be_bytes()[2..]);
+    bytes[6] = 0x80 | ((id >> 12) & 0x0F) as u8;
+    bytes[7] = (id >> 4) as u8;
+    bytes[8] = 0x80 | (id & 0x0F) as u8;
+    Uuid::from_bytes(bytes)
+}
+
+/// Recover a SnowID from a UUID created by `to_uuid_v8`
+pub fn from_uuid_v8(uuid: &Uuid) -> Result<u64, UuidError> {
+    check_shape(uuid, 8)?;
+    let bytes = uuid.as_bytes();
+    if bytes[8] & 0x30 != 0 || bytes[9..].iter().any(|&b| b != 0) {
+        return Err(UuidError::NonZeroPadding);
+    }
+
+    let mut high = [0u8; 8];
+    high[2..].copy_from_slice(&bytes[..6]);
+    Ok((u64::from_be_bytes(high) << 16)
+        | (((bytes[6] & 0x0F) as u64) << 12)
+        | ((bytes[7] as u64) << 4)
+        | (bytes[8] & 0x0F) as u64)
+}
+
+/// Embed a SnowID in a UUIDv7-shaped value
+///
+/// `unix_ts_ms` holds the ID's Unix millisecond timestamp from `extractor`,
+/// `rand_a` and `rand_b` hold the full 64-bit ID
+///
+/// Fails with `TimestampTooLarge` if the ID's timestamp does not fit the 48-bit UUIDv7 field
+/// (beyond year 10889), which `js_safe` layouts with a long tick can reach
+pub fn to_uuid_v7(id: u64, extractor: &SnowIDExtractor) -> Result<Uuid, UuidError> {
+    let unix_ms = extractor.timestamp_ms(id);
+    if unix_ms > MAX_V7_UNIX_MS {
+        return Err(UuidError::TimestampTooLarge { unix_ms });
+    }
+
+    let mut bytes = [0u8; 16];
+    bytes[..6].copy_from_slice(&unix_ms.to_be_bytes()[2..]);
+    bytes[6] = 0x70 | ((id >> 60) & 0x0F) as u8;
+    bytes[7] = (id >> 52) as u8;
+    bytes[8] = 0x80;
+    bytes[9] = ((id >> 48) & 0x0F) as u8;
+    bytes[10..].copy_from_slice(&id.to_be_bytes()[2..]);
+    Ok(Uuid::from_bytes(bytes))
+}
+
+/// Recover a SnowID from a UUID created by `to_uuid_v7`
+///
+/// Fails with `TimestampMismatch` if the UUID's timestamp disagrees with the ID under
+/// `extractor`, e.g. when it was created with a different layout or epoch
+pub fn from_uuid_v7(uuid: &Uuid, extractor: &SnowIDExtractor) -> Result<u64, UuidError> {
+    check_shape(uuid, 7)?;
+    let bytes = uuid.as_bytes();
+    if bytes[8] & 0x3F != 0 || bytes[9] & 0xF0 != 0 {
+        return Err(UuidError::NonZeroPadding);
+    }
+
+    let mut low = [0u8; 8];
+    low[2..].copy_from_slice(&bytes[10..]);
+    let id = (((bytes[6] & 0x0F) as u64) << 60)
+        | ((bytes[7] as u64) << 52)
+        | (((bytes[9] & 0x0F) as u64) << 48)
+        | u64::from_be_bytes(low);
+
+    let mut ts = [0u8; 8];
+    ts[2..].copy_from_slice(&bytes[..6]);
+    let unix_ms = u64::from_be_bytes(ts);
+    let expected = extractor.timestamp_ms(id);
+    if unix_ms != expected {
+        return Err(UuidError::TimestampMismatch { unix_ms, expected });
+    }
+    Ok(id)
+}
+
+fn check_shape(uuid: &Uuid, version: usize) -> Result<(), UuidError> {
+    let found = uuid.get_version_num();
+    if found != version {
+        return Err(UuidError::WrongVersion {
+            expected: version,
+            found,
+        });
+    }
+    if uuid.get_variant() != Variant::RFC4122 {
+        return Err(UuidError::InvalidVariant);
+    }
+    Ok(())
+}
+
+/// Errors when converting between SnowIDs and UUIDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UuidError {
+    /// SnowID timestamp does not fit the 48-bit UUIDv7 timestamp field
+    TimestampTooLarge { unix_ms: u64 },
+    /// UUID has a different version than the mapping produces
+    WrongVersion { expected: usize, found: usize },
+    /// UUID is not an RFC 9562 (RFC 4122 variant) UUID
+    InvalidVariant,
+    /// Bits outside the embedded SnowID are set, so the UUID was not created from one
+    NonZeroPadding,
+    /// UUIDv7 timestamp does not match the embedded ID's timestamp
+    TimestampMismatch { unix_ms: u64, expected: u64 },
+}
+
+impl fmt::Display for UuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UuidError::TimestampTooLarge { unix_ms } => write!(
+                f,
+                "Timestamp {} does not fit the 48-bit UUIDv7 field",
+                unix_ms
+            ),
+            UuidError::WrongVersion { expected, found } => {
+                write!(f, "Expected UUID version {}, found {}", expected, found)
+            }
+            UuidError::InvalidVariant => write!(f, "UUID variant is not RFC 9562"),
+            UuidError::NonZeroPadding => {
+                write!(f, "UUID has bits set outside the embedded SnowID")
+            }
+            UuidError::TimestampMismatch { unix_ms, expected } => write!(
+                f,
+                "UUID timestamp {} does not match SnowID timestamp {}",
+                unix_ms, expected
+            ),
+        }
+    }
+}
+
+impl Error for UuidError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SnowID, SnowIDConfig};
+
+    const IDS: [u64; 7] = [
+        0,
+        1,
+        0xF,
+        0xFFFF,
+        151819733950271234,
+        u64::MAX >> 1,
+        u64::MAX,
+    ];
+
+    #[test]
+    fn test_v8_roundtrip() {
+        for id in IDS {
+            let uuid = to_uuid_v8(id);
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Variant::RFC4122);
+            assert_eq!(from_uuid_v8(&uuid).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_v7_roundtrip() {
+        let extractor = SnowIDConfig::default().extractor();
+        for id in IDS
+            .into_iter()
+            .filter(|&id| id <= extractor.config().max_id())
+        {
+            let uuid = to_uuid_v7(id, &extractor).unwrap();
+            assert_eq!(uuid.get_version_num(), 7);
+            assert_eq!(uuid.get_variant(), Variant::RFC4122);
+            assert_eq!(from_uuid_v7(&uuid, &extractor).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_v7_carries_unix_timestamp() {
+        let generator = SnowID::new(5).unwrap();
+        let id = generator.generate();
+        let uuid = to_uuid_v7(id, &generator.extract).unwrap();
+
+        let (secs, nanos) = uuid.get_timestamp().unwrap().to_unix();
+        let unix_ms = secs * 1000 + nanos as u64 / 1_000_000;
+        assert_eq!(unix_ms, generator.extract.timestamp_ms(id));
+    }
+
+    #[test]
+    fn test_components_recoverable() {
+        let generator = SnowID::new(513).unwrap();
+        let id = generator.generate();
+        let expected = generator.extract.decompose(id);
+
+        let v8 = from_uuid_v8(&to_uuid_v8(id)).unwrap();
+        let v7 = from_uuid_v7(
+            &to_uuid_v7(id, &generator.extract).unwrap(),
+            &generator.extract,
+        )
+        .unwrap();
+        assert_eq!(generator.extract.decompose(v8), expected);
+        assert_eq!(generator.extract.decompose(v7), expected);
+        assert_eq!(generator.extract.node(v7), 513);
+    }
+
+    #[test]
+    fn test_order_preserved() {
+        let generator = SnowID::new(1).unwrap();
+        let ids: Vec<u64> = (0..1000).map(|_| generator.generate()).collect();
+        let v8: Vec<Uuid> = ids.iter().map(|&id| to_uuid_v8(id)).collect();
+        let v7: Vec<Uuid> = ids
+            .iter()
+            .map(|&id| to_uuid_v7(id, &generator.extract).unwrap())
+            .collect();
+        assert!(v8.windows(2).all(|w| w[0] < w[1]));
+        assert!(v7.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_rejects_foreign_uuids() {
+        let extractor = SnowIDConfig::default().extractor();
+        let v4 = Uuid::from_u128(0x9b2c_5e1a_3f4d_4c8e_a1b2_c3d4_e5f6_0718);
+        assert_eq!(
+            from_uuid_v8(&v4),
+            Err(UuidError::WrongVersion {
+                expected: 8,
+                found: 4
+            })
+        );
+        assert_eq!(
+            from_uuid_v8(&Uuid::from_u128(0x0000_0000_0000_8000_8000_0000_0000_0001)),
+            Err(UuidError::NonZeroPadding)
+        );
+        assert_eq!(
+            from_uuid_v8(&Uuid::from_u128(0x0000_0000_0000_8000_0000_0000_0000_0000)),
+            Err(UuidError::InvalidVariant)
+        );
+
+        // Same ID under a different epoch yields a different timestamp
+        let id = 151819733950271234;
+        let other = SnowIDConfig::builder().epoch(1).build().extractor();
+        assert!(matches!(
+            from_uuid_v7(&to_uuid_v7(id, &other).unwrap(), &extractor),
+            Err(UuidError::TimestampMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_v7_timestamp_too_large() {
+        // 31 timestamp bits of 2^20 ms reach far beyond the 48-bit UUIDv7 field
+        let cfg = SnowIDConfig::builder()
+            .js_safe(1 << 20, 10, 12)
+            .unwrap()
+            .build();
+        let extractor = cfg.extractor();
+        let id = cfg.max_id();
+        assert_eq!(
+            to_uuid_v7(id, &extractor),
+            Err(UuidError::TimestampTooLarge {
+                unix_ms: extractor.timestamp_ms(id)
+            })
+        );
+        assert!(to_uuid_v7(0, &extractor).is_ok());
+
+        // A far-future epoch saturates instead of overflowing
+        let late = SnowIDConfig::builder()
+            .epoch(u64::MAX - 1)
+            .build()
+            .extractor();
+        assert_eq!(late.timestamp_ms(cfg.max_id()), u64::MAX);
+        assert_eq!(
+            to_uuid_v7(cfg.max_id(), &late),
+            Err(UuidError::TimestampTooLarge { unix_ms: u64::MAX })
+        );
+    }
+}
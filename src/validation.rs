@@ -0,0 +1,140 @@
+//! Plausibility validation for untrusted incoming SnowIDs
+//!
+//! `SnowIDExtractor::validate` checks an ID against a `ValidationPolicy`:
+//! - Reserved bits above the layout (e.g. bits 53-63 of a JS-safe layout) must be zero
+//! - Timestamp must be after the epoch (and optionally after a minimum date)
+//! - Timestamp must not be too far in the future
+//! - Node must be in the allowed range or set
+
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Default tolerated clock skew for IDs from the future
+const DEFAULT_MAX_FUTURE: Duration = Duration::from_secs(60);
+
+/// Which node IDs are accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeFilter {
+    /// Any node the layout can represent
+    Any,
+    /// Nodes within an inclusive range
+    Range(RangeInclusive<u16>),
+    /// Nodes from an explicit set
+    Set(Vec<u16>),
+}
+
+impl NodeFilter {
+    /// Whether the node passes the filter
+    #[inline]
+    pub fn allows(&self, node: u16) -> bool {
+        match self {
+            NodeFilter::Any => true,
+            NodeFilter::Range(range) => range.contains(&node),
+            NodeFilter::Set(nodes) => nodes.contains(&node),
+        }
+    }
+}
+
+/// Rules an incoming ID must satisfy to be considered plausible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    pub(crate) max_future: Duration,
+    pub(crate) not_before_ms: Option<u64>,
+    pub(crate) nodes: NodeFilter,
+}
+
+impl ValidationPolicy {
+    /// Create a policy accepting any node and up to 60 seconds of clock skew
+    pub fn new() -> Self {
+        Self {
+            max_future: DEFAULT_MAX_FUTURE,
+            not_before_ms: None,
+            nodes: NodeFilter::Any,
+        }
+    }
+
+    /// Set how far in the future (clock skew) a timestamp may be
+    pub fn max_future(mut self, max_future: Duration) -> Self {
+        self.max_future = max_future;
+        self
+    }
+
+    /// Reject IDs created before the given Unix timestamp in milliseconds
+    pub fn not_before(mut self, unix_ms: u64) -> Self {
+        self.not_before_ms = Some(unix_ms);
+        self
+    }
+
+    /// Accept only nodes within the inclusive range
+    pub fn node_range(mut self, range: RangeInclusive<u16>) -> Self {
+        self.nodes = NodeFilter::Range(range);
+        self
+    }
+
+    /// Accept only the listed nodes
+    pub fn allowed_nodes(mut self, nodes: impl IntoIterator<Item = u16>) -> Self {
+        self.nodes = NodeFilter::Set(nodes.into_iter().collect());
+        self
+    }
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reason an ID was rejected by `SnowIDExtractor::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Bits above the configured layout are set
+    ReservedBitsSet { id: u64, max_id: u64 },
+    /// Timestamp component is zero, i.e. not after the epoch
+    TimestampAtEpoch,
+    /// Timestamp is before the policy's minimum date
+    TooOld { unix_ms: u64, not_before_ms: u64 },
+    /// Timestamp is further in the future than the allowed clock skew
+    TooFarInFuture { unix_ms: u64, max_unix_ms: u64 },
+    /// Node is not in the allowed range or set
+    NodeNotAllowed { node: u16 },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::ReservedBitsSet { id, max_id } => {
+                write!(
+                    f,
+                    "ID {} sets reserved bits above layout maximum {}",
+                    id, max_id
+                )
+            }
+            ValidationError::TimestampAtEpoch => {
+                write!(f, "ID timestamp is not after the epoch")
+            }
+            ValidationError::TooOld {
+                unix_ms,
+                not_before_ms,
+            } => write!(
+                f,
+                "ID timestamp {} is before the minimum of {}",
+                unix_ms, not_before_ms
+            ),
+            ValidationError::TooFarInFuture {
+                unix_ms,
+                max_unix_ms,
+            } => write!(
+                f,
+                "ID timestamp {} is too far in the future (maximum {})",
+                unix_ms, max_unix_ms
+            ),
+            ValidationError::NodeNotAllowed { node } => {
+                write!(f, "Node {} is not allowed", node)
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}